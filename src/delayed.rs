@@ -0,0 +1,336 @@
+//! Delayed rendering utility
+//!
+//! Formats are placed onto clipboard without data, which is produced only once some application
+//! requests it, via `WM_RENDERFORMAT`.
+//!
+//! This requires clipboard owner window, which is created on separate thread, together with message
+//! loop to serve render requests.
+//!
+//! Owner window can also display `CF_OWNERDISPLAY` content in clipboard viewers via [OwnerDisplay](trait.OwnerDisplay.html).
+
+use error_code::ErrorCode;
+
+use crate::{raw, Clipboard, SysResult};
+use crate::types::{c_int, c_uint, HWND, WPARAM, LPARAM, LRESULT, WNDPROC, MSG, RECT, PAINTSTRUCT};
+use crate::utils::RawMem;
+use crate::sys::{
+    CreateWindowExW,
+    DestroyWindow,
+    GetMessageW,
+    DispatchMessageW,
+    PostMessageW,
+    PostQuitMessage,
+    CallWindowProcW,
+    SetWindowLongPtrW,
+};
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::{mem, ptr};
+use std::sync::mpsc;
+use std::thread;
+
+const GWLP_WNDPROC: c_int = -4;
+const HWND_MESSAGE: isize = -3;
+const WM_DESTROY: c_uint = 0x0002;
+const WM_CLOSE: c_uint = 0x0010;
+const WM_RENDERFORMAT: c_uint = 0x0305;
+const WM_RENDERALLFORMATS: c_uint = 0x0306;
+const WM_PAINTCLIPBOARD: c_uint = 0x0309;
+const WM_SIZECLIPBOARD: c_uint = 0x030B;
+const WM_ASKCBFORMATNAME: c_uint = 0x030C;
+const OPEN_ATTEMPTS: usize = 10;
+
+type RenderFn = Box<dyn FnMut(u32) -> Option<Vec<u8>> + Send>;
+
+///Callbacks to display `CF_OWNERDISPLAY` content within clipboard viewer.
+///
+///Callbacks are invoked on thread of owner window and are never re-entered:
+///message, arriving while callback runs, is ignored.
+pub trait OwnerDisplay: Send + 'static {
+    #[allow(unused_variables)]
+    ///Handles `WM_PAINTCLIPBOARD`, requesting to paint client area of `viewer` window.
+    fn paint(&mut self, viewer: HWND, paint: &PAINTSTRUCT) {
+    }
+
+    #[allow(unused_variables)]
+    ///Handles `WM_SIZECLIPBOARD`, notifying about new size of client area of `viewer` window.
+    ///
+    ///Rectangle is empty if viewer is about to be destroyed or minimized.
+    fn size(&mut self, viewer: HWND, rect: &RECT) {
+    }
+
+    ///Handles `WM_ASKCBFORMATNAME`, returning name of format to be shown by viewer.
+    ///
+    ///Name is truncated to fit viewer's buffer.
+    fn format_name(&mut self) -> Option<String> {
+        None
+    }
+}
+
+struct State {
+    //Taken out while it runs, so that re-entrant message finds no callback instead of borrowed state
+    render: Option<RenderFn>,
+    formats: Vec<u32>,
+    display: Option<Box<dyn OwnerDisplay>>,
+}
+
+//Display is taken out of state while callback runs, same as render callback
+fn with_display<F: FnOnce(&mut dyn OwnerDisplay)>(cb: F) {
+    let display = STATE.with(|state| match state.try_borrow_mut() {
+        Ok(mut state) => state.as_mut().and_then(|state| state.display.take()),
+        Err(_) => None,
+    });
+
+    if let Some(mut display) = display {
+        cb(display.as_mut());
+        STATE.with(|state| if let Some(state) = state.borrow_mut().as_mut() {
+            state.display = Some(display);
+        });
+    }
+}
+
+//Memory of viewer messages is global memory handle, which must be locked to access
+unsafe fn with_global<T, F: FnOnce(&T)>(handle: LPARAM, cb: F) {
+    if let Some(handle) = ptr::NonNull::new(handle as *mut _) {
+        let mem = RawMem::from_borrowed(handle);
+        if let Ok((ptr, _lock)) = mem.lock() {
+            cb(&*(ptr.as_ptr() as *const T))
+        }
+    }
+}
+
+std::thread_local! {
+    //Window procedure runs on the thread owning window, hence state is only accessed there
+    static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+    //Kept outside of state, as it is needed for any message, even while callback runs
+    static ORIGINAL: Cell<WNDPROC> = const { Cell::new(None) };
+}
+
+fn render_format(format: u32) {
+    let render = STATE.with(|state| match state.try_borrow_mut() {
+        Ok(mut state) => state.as_mut().and_then(|state| state.render.take()),
+        Err(_) => None,
+    });
+    let mut render = match render {
+        Some(render) => render,
+        None => return,
+    };
+
+    let data = render(format);
+    STATE.with(|state| if let Some(state) = state.borrow_mut().as_mut() {
+        state.render = Some(render);
+    });
+
+    if let Some(data) = data {
+        let _ = raw::set_without_clear(format, &data);
+    }
+}
+
+unsafe extern "system" fn render_proc(window: HWND, msg: c_uint, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+    match msg {
+        //Clipboard is already open by application requesting data
+        WM_RENDERFORMAT => {
+            render_format(w_param as u32);
+            0
+        },
+        //Sent before window is destroyed, while it is still clipboard owner
+        WM_RENDERALLFORMATS => {
+            if let Ok(_clip) = Clipboard::new_attempts_for(window, OPEN_ATTEMPTS) {
+                //Someone else could take clipboard in between
+                if raw::get_owner().map(|owner| owner.as_ptr()) == Some(window) {
+                    let formats = STATE.with(|state| state.try_borrow().ok().and_then(|state| state.as_ref().map(|state| state.formats.clone())).unwrap_or_default());
+                    for format in formats {
+                        render_format(format);
+                    }
+                }
+            }
+            0
+        },
+        WM_PAINTCLIPBOARD => {
+            with_global(l_param, |paint: &PAINTSTRUCT| with_display(|display| display.paint(w_param as HWND, paint)));
+            0
+        },
+        WM_SIZECLIPBOARD => {
+            with_global(l_param, |rect: &RECT| with_display(|display| display.size(w_param as HWND, rect)));
+            0
+        },
+        WM_ASKCBFORMATNAME => {
+            let mut name = None;
+            with_display(|display| name = display.format_name());
+            let buffer = l_param as *mut u16;
+            let buffer_len = w_param;
+            if let (Some(name), false) = (name, buffer.is_null() || buffer_len == 0) {
+                let mut len = 0;
+                //Truncate by characters, so that surrogate pair is never split
+                for ch in name.chars() {
+                    let mut encoded = [0u16; 2];
+                    let encoded = ch.encode_utf16(&mut encoded);
+                    if len + encoded.len() >= buffer_len {
+                        break;
+                    }
+                    for unit in encoded.iter() {
+                        buffer.add(len).write(*unit);
+                        len += 1;
+                    }
+                }
+                buffer.add(len).write(0);
+            }
+            0
+        },
+        _ => {
+            if msg == WM_DESTROY {
+                PostQuitMessage(0);
+            }
+
+            CallWindowProcW(ORIGINAL.with(Cell::get), window, msg, w_param, l_param)
+        }
+    }
+}
+
+fn create_window(formats: Vec<u32>, render: RenderFn, display: Option<Box<dyn OwnerDisplay>>) -> SysResult<HWND> {
+    //utf-16 "STATIC"
+    const CLASS: [u16; 7] = [83, 84, 65, 84, 73, 67, 0];
+
+    let window = unsafe {
+        CreateWindowExW(0, CLASS.as_ptr(), ptr::null(), 0, 0, 0, 0, 0, HWND_MESSAGE as HWND, ptr::null_mut(), ptr::null_mut(), ptr::null_mut())
+    };
+    if window.is_null() {
+        return Err(ErrorCode::last_system());
+    }
+
+    let original = unsafe {
+        SetWindowLongPtrW(window, GWLP_WNDPROC, render_proc as *const () as isize)
+    };
+    if original == 0 {
+        let error = ErrorCode::last_system();
+        unsafe {
+            DestroyWindow(window);
+        }
+        return Err(error);
+    }
+
+    let is_owner_display = display.is_some();
+    ORIGINAL.with(|cell| cell.set(unsafe { mem::transmute::<isize, WNDPROC>(original) }));
+    STATE.with(|state| *state.borrow_mut() = Some(State {
+        render: Some(render),
+        formats: formats.clone(),
+        display,
+    }));
+
+    let result = Clipboard::new_attempts_for(window, OPEN_ATTEMPTS).and_then(|clip| {
+        clip.empty()?;
+        for format in formats {
+            clip.set_delayed(format)?;
+        }
+        if is_owner_display {
+            clip.set_delayed(crate::formats::CF_OWNERDISPLAY)?;
+        }
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => Ok(window),
+        Err(error) => {
+            unsafe {
+                DestroyWindow(window);
+            }
+            Err(error)
+        }
+    }
+}
+
+///Delayed rendering guard.
+///
+///Owns clipboard window, which produces data of advertised formats on request, using provided
+///callback.
+///
+///Callback is invoked on separate thread, which lives as long as guard.
+///Returning `None` or empty data leaves format without data.
+///Callback is never re-entered: request, arriving while it runs, is left without data.
+///It must not panic, as it is invoked within window procedure, where unwinding aborts process.
+///
+///On drop, all formats are rendered, if window is still clipboard owner, so that data remains available.
+pub struct DelayedRender {
+    window: usize,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl DelayedRender {
+    ///Empties clipboard and advertises `formats` to be rendered by `render` on request.
+    ///
+    ///Owning thread must stay alive to serve render requests, hence guard must be kept for as long as data is needed.
+    pub fn new<F: FnMut(u32) -> Option<Vec<u8>> + Send + 'static>(formats: &[u32], render: F) -> SysResult<Self> {
+        Self::create(formats, Box::new(render), None)
+    }
+
+    ///Same as [new](#method.new), but additionally advertises `CF_OWNERDISPLAY`, which is displayed by `display`.
+    pub fn with_owner_display<F: FnMut(u32) -> Option<Vec<u8>> + Send + 'static, D: OwnerDisplay>(formats: &[u32], render: F, display: D) -> SysResult<Self> {
+        Self::create(formats, Box::new(render), Some(Box::new(display)))
+    }
+
+    fn create(formats: &[u32], render: RenderFn, display: Option<Box<dyn OwnerDisplay>>) -> SysResult<Self> {
+        let formats = formats.to_vec();
+        let (sender, receiver) = mpsc::channel();
+
+        let thread = thread::spawn(move || {
+            match create_window(formats, render, display) {
+                Ok(window) => {
+                    let _ = sender.send(Ok(window as usize));
+                },
+                Err(error) => {
+                    let _ = sender.send(Err(error));
+                    return;
+                }
+            }
+
+            let mut msg = mem::MaybeUninit::<MSG>::uninit();
+            //Sent messages, such as WM_RENDERFORMAT, are dispatched within GetMessageW
+            while unsafe { GetMessageW(msg.as_mut_ptr(), ptr::null_mut(), 0, 0) } > 0 {
+                unsafe {
+                    DispatchMessageW(msg.as_ptr());
+                }
+            }
+        });
+
+        let result = match receiver.recv() {
+            Ok(result) => result,
+            //Thread can only exit without result if it panicked, hence panic is propagated to caller
+            Err(_) => match thread.join() {
+                Err(panic) => std::panic::resume_unwind(panic),
+                Ok(()) => unreachable!(),
+            },
+        };
+
+        match result {
+            Ok(window) => Ok(Self {
+                window,
+                thread: Some(thread),
+            }),
+            Err(error) => {
+                let _ = thread.join();
+                Err(error)
+            }
+        }
+    }
+
+    #[inline(always)]
+    ///Returns clipboard owner window.
+    pub fn window(&self) -> HWND {
+        self.window as HWND
+    }
+}
+
+impl Drop for DelayedRender {
+    fn drop(&mut self) {
+        unsafe {
+            PostMessageW(self.window as HWND, WM_CLOSE, 0, 0);
+        }
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
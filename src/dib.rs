@@ -0,0 +1,304 @@
+//! Device independent bitmap utilities.
+//!
+//! Clipboard stores DIB as `BITMAPINFOHEADER` followed by color table and pixels, which is
+//! essentially BMP file without `BITMAPFILEHEADER`.
+
+use crate::SysResult;
+use crate::types::{DWORD, BITMAPINFOHEADER, BITMAPFILEHEADER, RGBQUAD};
+use crate::utils::invalid_data;
+
+use core::{mem, ptr};
+
+///Pixels are compressed with bit fields, specified by color masks after header.
+pub const BI_BITFIELDS: DWORD = 3;
+///Pixels are JPEG image.
+pub const BI_JPEG: DWORD = 4;
+///Pixels are PNG image.
+pub const BI_PNG: DWORD = 5;
+
+///Size of `BITMAPV5HEADER`
+pub const V5_HEADER_LEN: usize = 124;
+//Logical color space is sRGB
+const LCS_SRGB: DWORD = 0x73524742;
+//Saturation preserving rendering intent
+const LCS_GM_IMAGES: DWORD = 4;
+
+const HEADER_LEN: usize = mem::size_of::<BITMAPINFOHEADER>();
+//Offset of bV5AlphaMask, which follows RGB masks
+const ALPHA_MASK_OFFSET: usize = HEADER_LEN + 3 * mem::size_of::<DWORD>();
+const FILE_HEADER_LEN: usize = mem::size_of::<BITMAPFILEHEADER>();
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Kind of compressed image embedded into DIB.
+pub enum ImageKind {
+    ///JPEG image.
+    Jpeg,
+    ///PNG image.
+    Png,
+}
+
+///Device independent bitmap.
+pub struct Image {
+    data: alloc::vec::Vec<u8>,
+}
+
+impl Image {
+    ///Creates new instance from DIB bytes, as they are stored on clipboard.
+    ///
+    ///Returns `None` if data cannot hold `BITMAPINFOHEADER`.
+    pub fn new(data: alloc::vec::Vec<u8>) -> Option<Self> {
+        if data.len() < HEADER_LEN {
+            return None;
+        }
+
+        let this = Self {
+            data
+        };
+        match (this.header().biSize as usize) < HEADER_LEN {
+            true => None,
+            false => Some(this),
+        }
+    }
+
+    ///Creates new instance from BMP file, stripping its file header.
+    ///
+    ///Returns `None` if data is not BMP file.
+    pub fn from_bmp(mut data: alloc::vec::Vec<u8>) -> Option<Self> {
+        if data.len() < FILE_HEADER_LEN || !data.starts_with(b"BM") {
+            return None;
+        }
+
+        data.drain(..FILE_HEADER_LEN);
+        Self::new(data)
+    }
+
+    ///Creates new instance embedding PNG image, which is `BI_PNG` compressed DIB.
+    ///
+    ///Returns `None` if data is not PNG image.
+    pub fn from_png(png: &[u8]) -> Option<Self> {
+        const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+        //Signature, chunk length, IHDR, width and height
+        const DIMENSIONS_END: usize = 8 + 4 + 4 + 4 + 4;
+
+        if png.len() < DIMENSIONS_END || !png.starts_with(SIGNATURE) || &png[12..16] != b"IHDR" {
+            return None;
+        }
+
+        let width = u32::from_be_bytes([png[16], png[17], png[18], png[19]]);
+        let height = u32::from_be_bytes([png[20], png[21], png[22], png[23]]);
+
+        let header = BITMAPINFOHEADER {
+            biSize: HEADER_LEN as DWORD,
+            biWidth: width as i32,
+            biHeight: height as i32,
+            biPlanes: 1,
+            biBitCount: 0,
+            biCompression: BI_PNG,
+            biSizeImage: png.len() as DWORD,
+            biXPelsPerMeter: 0,
+            biYPelsPerMeter: 0,
+            biClrUsed: 0,
+            biClrImportant: 0,
+        };
+
+        let mut data = alloc::vec::Vec::with_capacity(HEADER_LEN + png.len());
+        unsafe {
+            ptr::write_unaligned(data.as_mut_ptr() as *mut BITMAPINFOHEADER, header);
+            data.set_len(HEADER_LEN);
+        }
+        data.extend_from_slice(png);
+
+        Self::new(data)
+    }
+
+    ///Creates DIB with `BITMAPV5HEADER` out of 32-bit BGRA pixels, preserving alpha channel.
+    ///
+    ///Pixels are expected to be in rows from top to bottom.
+    ///
+    ///Returns `None` if `pixels` size doesn't match `width` and `height`.
+    pub fn from_bgra(width: u32, height: u32, pixels: &[u8]) -> Option<Self> {
+        if width > i32::MAX as u32 || height > i32::MAX as u32 {
+            return None;
+        }
+        let row_len = (width as usize).checked_mul(4)?;
+        if row_len.checked_mul(height as usize)? != pixels.len() {
+            return None;
+        }
+
+        let header = BITMAPINFOHEADER {
+            biSize: V5_HEADER_LEN as DWORD,
+            biWidth: width as i32,
+            //Bottom-up image, as top-down is not understood by every application
+            biHeight: height as i32,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_BITFIELDS,
+            biSizeImage: pixels.len() as DWORD,
+            biXPelsPerMeter: 0,
+            biYPelsPerMeter: 0,
+            biClrUsed: 0,
+            biClrImportant: 0,
+        };
+
+        let mut data = alloc::vec::Vec::with_capacity(V5_HEADER_LEN + pixels.len());
+        unsafe {
+            ptr::write_unaligned(data.as_mut_ptr() as *mut BITMAPINFOHEADER, header);
+            data.set_len(HEADER_LEN);
+        }
+        //Red, green, blue and alpha masks
+        for mask in [0x00FF0000u32, 0x0000FF00, 0x000000FF, 0xFF000000].iter() {
+            data.extend_from_slice(&mask.to_le_bytes());
+        }
+        data.extend_from_slice(&LCS_SRGB.to_le_bytes());
+        //Endpoints and gamma are ignored for sRGB
+        data.resize(data.len() + 36 + 3 * mem::size_of::<DWORD>(), 0);
+        data.extend_from_slice(&LCS_GM_IMAGES.to_le_bytes());
+        //Profile data, profile size and reserved
+        data.resize(V5_HEADER_LEN, 0);
+
+        if row_len != 0 {
+            for row in pixels.chunks_exact(row_len).rev() {
+                data.extend_from_slice(row);
+            }
+        }
+
+        Self::new(data)
+    }
+
+    #[inline(always)]
+    ///Returns DIB bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline(always)]
+    ///Returns DIB bytes.
+    pub fn into_bytes(self) -> alloc::vec::Vec<u8> {
+        self.data
+    }
+
+    #[inline(always)]
+    ///Returns copy of image header.
+    pub fn header(&self) -> BITMAPINFOHEADER {
+        unsafe {
+            ptr::read_unaligned(self.data.as_ptr() as *const BITMAPINFOHEADER)
+        }
+    }
+
+    #[inline(always)]
+    ///Returns image width.
+    pub fn width(&self) -> i32 {
+        self.header().biWidth
+    }
+
+    #[inline(always)]
+    ///Returns image height.
+    ///
+    ///Negative height indicates top-down image.
+    pub fn height(&self) -> i32 {
+        self.header().biHeight
+    }
+
+    ///Returns alpha mask, if header is `BITMAPV4HEADER` or later and mask is specified.
+    pub fn alpha_mask(&self) -> Option<u32> {
+        if (self.header().biSize as usize) < ALPHA_MASK_OFFSET + mem::size_of::<DWORD>() {
+            return None;
+        }
+
+        let mask = self.data.get(ALPHA_MASK_OFFSET..ALPHA_MASK_OFFSET + mem::size_of::<DWORD>())?;
+        match u32::from_le_bytes([mask[0], mask[1], mask[2], mask[3]]) {
+            0 => None,
+            mask => Some(mask),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns whether image has alpha channel, specified by its alpha mask.
+    pub fn has_alpha(&self) -> bool {
+        self.alpha_mask().is_some()
+    }
+
+    #[inline(always)]
+    ///Returns whether rows are stored from top to bottom, which is indicated by negative height.
+    pub fn is_top_down(&self) -> bool {
+        self.height() < 0
+    }
+
+    ///Returns offset of pixels, which is size of header and color table.
+    pub fn pixels_offset(&self) -> usize {
+        let header = self.header();
+        let colors = if header.biClrUsed != 0 {
+            header.biClrUsed as usize
+        } else if header.biBitCount <= 8 && header.biBitCount != 0 {
+            1 << header.biBitCount
+        } else {
+            0
+        };
+        //Color masks follow header, unless they are part of the header itself
+        let masks = if header.biCompression == BI_BITFIELDS && header.biSize as usize == HEADER_LEN {
+            3 * mem::size_of::<DWORD>()
+        } else {
+            0
+        };
+
+        header.biSize as usize + masks + colors * mem::size_of::<RGBQUAD>()
+    }
+
+    ///Returns image embedded as pixels, if compression is `BI_JPEG` or `BI_PNG`.
+    pub fn embedded_image(&self) -> Option<(ImageKind, &[u8])> {
+        let header = self.header();
+        let kind = match header.biCompression {
+            BI_JPEG => ImageKind::Jpeg,
+            BI_PNG => ImageKind::Png,
+            _ => return None,
+        };
+
+        let image = self.data.get(self.pixels_offset()..)?;
+        match header.biSizeImage as usize {
+            0 => Some((kind, image)),
+            size => image.get(..size).map(|image| (kind, image)),
+        }
+    }
+
+    ///Writes image as BMP file, appending it to `out` and returning number of bytes written.
+    ///
+    ///Fails with `ERROR_INVALID_DATA` if image is compressed as JPEG or PNG, in which case use
+    ///[embedded_image](#method.embedded_image)
+    pub fn write_bmp(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+        let file_header = self.file_header()?;
+
+        out.reserve(FILE_HEADER_LEN + self.data.len());
+        out.extend_from_slice(&file_header);
+        out.extend_from_slice(&self.data);
+        Ok(FILE_HEADER_LEN + self.data.len())
+    }
+
+    #[cfg(feature = "std")]
+    ///Writes image as BMP file, returning number of bytes written.
+    ///
+    ///Fails with `InvalidData` if image is compressed as JPEG or PNG, in which case use
+    ///[embedded_image](#method.embedded_image)
+    pub fn write<W: std::io::Write>(&self, mut out: W) -> std::io::Result<usize> {
+        let file_header = match self.file_header() {
+            Ok(file_header) => file_header,
+            Err(_) => return Err(std::io::ErrorKind::InvalidData.into()),
+        };
+
+        out.write_all(&file_header)?;
+        out.write_all(&self.data)?;
+        Ok(FILE_HEADER_LEN + self.data.len())
+    }
+
+    fn file_header(&self) -> SysResult<[u8; FILE_HEADER_LEN]> {
+        if let BI_JPEG | BI_PNG = self.header().biCompression {
+            return Err(invalid_data());
+        }
+
+        let mut result = [0u8; FILE_HEADER_LEN];
+        result[..2].copy_from_slice(&u16::to_le_bytes(0x4d42));
+        result[2..6].copy_from_slice(&u32::to_le_bytes((FILE_HEADER_LEN + self.data.len()) as u32));
+        //2 * u16 of 0 are reserved
+        result[10..].copy_from_slice(&u32::to_le_bytes((FILE_HEADER_LEN + self.pixels_offset()) as u32));
+        Ok(result)
+    }
+}
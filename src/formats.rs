@@ -9,6 +9,8 @@ use crate::{SysResult, Getter, Setter};
 use crate::types::c_uint;
 
 use core::num::NonZeroU32;
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU8, AtomicU32, Ordering};
 
 ///Format trait
 pub trait Format {
@@ -103,12 +105,287 @@ pub const CF_UNICODETEXT: c_uint = 13;
 ///Represents audio data in one of the standard wave formats.
 pub const CF_WAVE: c_uint = 12;
 
+macro_rules! define_standard_format {
+    ($($variant:ident => $code:ident,)+) => {
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        ///Standard clipboard format, allowing typed handling of `CF_*` constants.
+        pub enum StandardFormat {
+            $(
+                #[doc = concat!("`", stringify!($code), "`")]
+                $variant,
+            )+
+            ///Private format in range `CF_PRIVATEFIRST..=CF_PRIVATELAST`, carrying offset from `CF_PRIVATEFIRST`.
+            Private(u32),
+            ///GDI object format in range `CF_GDIOBJFIRST..=CF_GDIOBJLAST`, carrying offset from `CF_GDIOBJFIRST`.
+            GdiObj(u32),
+        }
+
+        impl StandardFormat {
+            ///Converts `format` code into standard format, if it is one.
+            pub const fn from_u32(format: u32) -> Option<Self> {
+                match format {
+                    $($code => Some(Self::$variant),)+
+                    CF_PRIVATEFIRST..=CF_PRIVATELAST => Some(Self::Private(format - CF_PRIVATEFIRST)),
+                    CF_GDIOBJFIRST..=CF_GDIOBJLAST => Some(Self::GdiObj(format - CF_GDIOBJFIRST)),
+                    _ => None,
+                }
+            }
+
+            ///Returns format code.
+            ///
+            ///Offset of `Private` and `GdiObj` is added to start of its range as it is.
+            pub const fn as_u32(&self) -> u32 {
+                match self {
+                    $(Self::$variant => $code,)+
+                    Self::Private(offset) => CF_PRIVATEFIRST + *offset,
+                    Self::GdiObj(offset) => CF_GDIOBJFIRST + *offset,
+                }
+            }
+        }
+    }
+}
+
+define_standard_format!(
+    Bitmap => CF_BITMAP,
+    Dib => CF_DIB,
+    DibV5 => CF_DIBV5,
+    Dif => CF_DIF,
+    DspBitmap => CF_DSPBITMAP,
+    DspEnhMetafile => CF_DSPENHMETAFILE,
+    DspMetafilePict => CF_DSPMETAFILEPICT,
+    DspText => CF_DSPTEXT,
+    EnhMetafile => CF_ENHMETAFILE,
+    Hdrop => CF_HDROP,
+    Locale => CF_LOCALE,
+    MetafilePict => CF_METAFILEPICT,
+    OemText => CF_OEMTEXT,
+    OwnerDisplay => CF_OWNERDISPLAY,
+    Palette => CF_PALETTE,
+    PenData => CF_PENDATA,
+    Riff => CF_RIFF,
+    Sylk => CF_SYLK,
+    Text => CF_TEXT,
+    Tiff => CF_TIFF,
+    UnicodeText => CF_UNICODETEXT,
+    Wave => CF_WAVE,
+);
+
+impl StandardFormat {
+    #[inline(always)]
+    ///Returns name of format, e.g. `CF_TEXT` or `CF_PRIVATE1`.
+    ///
+    ///Refer to [format_name_big](../raw/fn.format_name_big.html)
+    pub fn name(&self) -> alloc::string::String {
+        crate::raw::format_name_big(self.as_u32()).unwrap_or_default()
+    }
+}
+
+impl From<StandardFormat> for u32 {
+    #[inline(always)]
+    fn from(value: StandardFormat) -> Self {
+        value.as_u32()
+    }
+}
+
+///Returns whether data of `format` is handle to GDI object (or absent as for `CF_OWNERDISPLAY`), rather than global memory.
+///
+///Such data cannot be copied as bytes.
+///
+///`CF_METAFILEPICT`, `CF_DSPMETAFILEPICT` and `CF_PRIVATEFIRST..=CF_PRIVATELAST` range are global memory,
+///but it refers to other handles, use [is_self_contained](fn.is_self_contained.html) to exclude them too.
+pub const fn is_handle(format: c_uint) -> bool {
+    match format {
+        CF_BITMAP | CF_PALETTE | CF_ENHMETAFILE | CF_OWNERDISPLAY | CF_DSPBITMAP | CF_DSPENHMETAFILE => true,
+        CF_GDIOBJFIRST..=CF_GDIOBJLAST => true,
+        _ => false,
+    }
+}
+
+///Returns whether data of `format` is global memory, which bytes are meaningful on their own.
+///
+///Unlike [is_handle](fn.is_handle.html) it also excludes global memory referring to other handles,
+///i.e. `CF_METAFILEPICT`, `CF_DSPMETAFILEPICT` and `CF_PRIVATEFIRST..=CF_PRIVATELAST` range.
+pub const fn is_self_contained(format: c_uint) -> bool {
+    match format {
+        CF_METAFILEPICT | CF_DSPMETAFILEPICT | CF_PRIVATEFIRST..=CF_PRIVATELAST => false,
+        format => !is_handle(format),
+    }
+}
+
+///Returns whether `format` is OLE private format (e.g. `Ole Private Data`).
+///
+///Such formats are used internally by OLE and are meaningless outside of application that set them,
+///which makes them not worth capturing or transferring.
+///
+///Checked names: `Ole Private Data`, `DataObject`, `OwnerLink`, `ObjectLink`.
+///
+///`Object Descriptor` and `Link Source Descriptor` are not considered private, as they describe
+///embedded or linked object and are required to paste it (see [office](office/index.html)).
+pub fn is_ole_private(format: c_uint) -> bool {
+    const NAMES: &[&str] = &["Ole Private Data", "DataObject", "OwnerLink", "ObjectLink"];
+
+    if format < 0xC000 {
+        return false;
+    }
+
+    let mut buffer = [0u8; 64];
+    match crate::raw::format_name(format, buffer.as_mut_slice().into()) {
+        Some(name) => NAMES.contains(&name),
+        None => false,
+    }
+}
+
+///Collects identifiers of all formats available on clipboard.
+///
+///Unlike [EnumFormats](../raw/struct.EnumFormats.html) clipboard is opened and closed by this function,
+///unless it is already open by [Clipboard](../struct.Clipboard.html) instance on calling thread.
+///
+///Data is not captured, use [ClipboardSnapshot](../snapshot/struct.ClipboardSnapshot.html) for that.
+pub fn snapshot() -> SysResult<alloc::vec::Vec<u32>> {
+    let _clip = crate::Clipboard::new_or_reuse(10)?;
+    Ok(crate::raw::EnumFormats::new().collect())
+}
+
+///Collects identifiers of all formats available on clipboard together with their names.
+///
+///Name is empty if it cannot be retrieved.
+///Refer to [snapshot](fn.snapshot.html) for details.
+pub fn snapshot_named() -> SysResult<alloc::vec::Vec<(u32, alloc::string::String)>> {
+    let formats = snapshot()?;
+    Ok(formats.into_iter().map(|format| (format, crate::raw::format_name_big(format).unwrap_or_default())).collect())
+}
+
+///Registered format, which is registered on first use and shared across threads.
+///
+///As registration is idempotent, concurrent first use may register format more than once, but
+///always results in the same identifier.
+///
+///```
+///use clipboard_win::formats::LazyFormat;
+///
+///static PNG: LazyFormat = LazyFormat::new("PNG");
+///
+///assert_eq!(PNG.id(), clipboard_win::register_format("PNG").unwrap().get());
+///```
+pub struct LazyFormat {
+    name: &'static str,
+    id: AtomicU32,
+}
+
+impl LazyFormat {
+    #[inline(always)]
+    ///Creates new instance for format `name`
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            id: AtomicU32::new(0),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns format name
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    ///Returns format identifier, registering it if necessary.
+    ///
+    ///Returns `None` if registration fails, in which case it is attempted again on next call.
+    pub fn get(&self) -> Option<NonZeroU32> {
+        match NonZeroU32::new(self.id.load(Ordering::Acquire)) {
+            Some(id) => Some(id),
+            None => {
+                let id = crate::raw::register_format(self.name)?;
+                self.id.store(id.get(), Ordering::Release);
+                Some(id)
+            }
+        }
+    }
+
+    #[inline(always)]
+    ///Returns format identifier, registering it if necessary.
+    ///
+    ///Returns 0 if registration fails, which is not valid format.
+    pub fn id(&self) -> u32 {
+        self.get().map_or(0, NonZeroU32::get)
+    }
+}
+
+const FORMAT_CACHE_SIZE: usize = 32;
+const SLOT_EMPTY: u8 = 0;
+const SLOT_BUSY: u8 = 1;
+const SLOT_READY: u8 = 2;
+
+//Slot is filled once and never changes afterwards, hence name can be read once it is ready
+struct CachedFormat {
+    state: AtomicU8,
+    name: UnsafeCell<&'static str>,
+    id: AtomicU32,
+}
+
+unsafe impl Sync for CachedFormat {}
+
+impl CachedFormat {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY: Self = Self {
+        state: AtomicU8::new(SLOT_EMPTY),
+        name: UnsafeCell::new(""),
+        id: AtomicU32::new(0),
+    };
+}
+
+static FORMAT_CACHE: [CachedFormat; FORMAT_CACHE_SIZE] = [CachedFormat::EMPTY; FORMAT_CACHE_SIZE];
+
+///Registers format `name`, caching its identifier for subsequent calls.
+///
+///Windows already returns the same identifier for the same name, but cache avoids calling
+///`RegisterClipboardFormatW` on every use.
+///
+///Cache holds up to 32 names, after which other names are registered on every call.
+///Failed registration is not cached, hence it is attempted again on next call.
+///For formats known at compile time prefer [LazyFormat](struct.LazyFormat.html).
+pub fn register_cached(name: &'static str) -> Option<NonZeroU32> {
+    //Slots are claimed in order, hence there is nothing past empty slot
+    for slot in FORMAT_CACHE.iter() {
+        match slot.state.load(Ordering::Acquire) {
+            SLOT_READY => if unsafe { *slot.name.get() } == name {
+                return NonZeroU32::new(slot.id.load(Ordering::Relaxed));
+            },
+            SLOT_BUSY => continue,
+            _ => break,
+        }
+    }
+
+    let id = crate::raw::register_format(name)?;
+    //Concurrent first use may cache the same name twice, which is harmless
+    for slot in FORMAT_CACHE.iter() {
+        if slot.state.compare_exchange(SLOT_EMPTY, SLOT_BUSY, Ordering::Acquire, Ordering::Relaxed).is_ok() {
+            unsafe {
+                *slot.name.get() = name;
+            }
+            slot.id.store(id.get(), Ordering::Relaxed);
+            slot.state.store(SLOT_READY, Ordering::Release);
+            break;
+        }
+    }
+
+    Some(id)
+}
+
 #[derive(Copy, Clone)]
 ///Format to write/read from clipboard as raw bytes
 ///
 ///Has to be initialized with format `id`
 pub struct RawData(pub c_uint);
 
+impl RawData {
+    #[inline(always)]
+    ///Reads raw bytes into newly allocated buffer.
+    pub fn read_clipboard_vec(&self) -> SysResult<alloc::vec::Vec<u8>> {
+        crate::raw::get_owned(self.0)
+    }
+}
+
 impl<T: AsRef<[u8]>> Setter<T> for RawData {
     #[inline(always)]
     fn write_clipboard(&self, data: &T) -> SysResult<()> {
@@ -134,8 +411,144 @@ impl From<&RawData> for u32 {
 ///Format to read/write unicode string.
 ///
 ///Refer to `Getter` and `Setter`
+///
+///Note that `read_clipboard` appends to provided `String`, unlike most other clipboard libraries.
+///Use [load](#method.load) or [get_clipboard_string](../fn.get_clipboard_string.html) to get only current content.
 pub struct Unicode;
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Style of line breaks within text.
+pub enum NewlineStyle {
+    ///Text has no line breaks.
+    None,
+    ///All line breaks are `\r\n`.
+    Crlf,
+    ///All line breaks are `\n`.
+    Lf,
+    ///Text contains both `\r\n` and `\n` line breaks.
+    Mixed,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Information about text read from clipboard.
+pub struct TextInfo {
+    ///Number of bytes read.
+    pub bytes: usize,
+    ///Whether text ends with line break.
+    pub had_trailing_newline: bool,
+    ///Style of line breaks within text.
+    pub newline_style: NewlineStyle,
+}
+
+impl TextInfo {
+    fn new(text: &str) -> Self {
+        let mut crlf = false;
+        let mut lf = false;
+        let mut prev = 0u8;
+
+        for byte in text.bytes() {
+            if byte == b'\n' {
+                match prev == b'\r' {
+                    true => crlf = true,
+                    false => lf = true,
+                }
+            }
+            prev = byte;
+        }
+
+        Self {
+            bytes: text.len(),
+            had_trailing_newline: text.ends_with('\n'),
+            newline_style: match (crlf, lf) {
+                (false, false) => NewlineStyle::None,
+                (true, false) => NewlineStyle::Crlf,
+                (false, true) => NewlineStyle::Lf,
+                (true, true) => NewlineStyle::Mixed,
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Kind of text, determined by best-effort heuristics.
+pub enum TextKind {
+    ///Text that doesn't fit any other kind.
+    Plain,
+    ///Single line starting with URL scheme (e.g. `https://`, `mailto:`).
+    Url,
+    ///Single line absolute Windows path, starting with drive letter or as UNC path.
+    Path,
+    ///Multiple lines, at least half of which are indented or end with one of `;{}()[]:,`
+    Code,
+}
+
+impl TextKind {
+    ///Classifies `text`, ignoring leading and trailing whitespaces.
+    pub fn classify(text: &str) -> Self {
+        const SCHEMES: &[&str] = &["http://", "https://", "ftp://", "file://", "mailto:"];
+
+        let text = text.trim();
+        if !text.contains('\n') {
+            let bytes = text.as_bytes();
+            if SCHEMES.iter().any(|scheme| bytes.len() > scheme.len() && bytes[..scheme.len()].eq_ignore_ascii_case(scheme.as_bytes())) {
+                return TextKind::Url;
+            }
+
+            let is_drive = bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && (bytes[2] == b'\\' || bytes[2] == b'/');
+            if is_drive || text.starts_with("\\\\") {
+                return TextKind::Path;
+            }
+
+            return TextKind::Plain;
+        }
+
+        let mut lines = 0;
+        let mut code_lines = 0;
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            lines += 1;
+
+            let is_indented = line.starts_with(' ') || line.starts_with('\t');
+            let is_code_end = line.trim_end().ends_with(|ch| ";{}()[]:,".contains(ch));
+            if is_indented || is_code_end {
+                code_lines += 1;
+            }
+        }
+
+        match lines > 1 && code_lines * 2 >= lines {
+            true => TextKind::Code,
+            false => TextKind::Plain,
+        }
+    }
+}
+
+impl Unicode {
+    ///Reads unicode string, appending it to `out`, and returns information about read text.
+    pub fn read_clipboard_info(&self, out: &mut alloc::string::String) -> SysResult<TextInfo> {
+        let start = out.len();
+        self.read_clipboard(out)?;
+        Ok(TextInfo::new(&out[start..]))
+    }
+
+    #[inline]
+    ///Reads unicode string into `out`, replacing its content while re-using its capacity.
+    ///
+    ///Unlike `read_clipboard`, which appends, `out` is cleared first.
+    ///Returns number of bytes read.
+    pub fn load(&self, out: &mut alloc::string::String) -> SysResult<usize> {
+        out.clear();
+        self.read_clipboard(out)
+    }
+
+    #[inline(always)]
+    ///Reads unicode string, appending it to `out`, while keeping interior null characters.
+    ///
+    ///Unlike `read_clipboard`, which stops at first null character, whole clipboard content is read.
+    ///Refer to [get_string_full](../raw/fn.get_string_full.html) for details
+    pub fn read_clipboard_full(&self, out: &mut alloc::string::String) -> SysResult<usize> {
+        crate::raw::get_string_full(unsafe { out.as_mut_vec() })
+    }
+}
+
 impl Getter<alloc::vec::Vec<u8>> for Unicode {
     #[inline(always)]
     fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
@@ -157,6 +570,13 @@ impl<T: AsRef<str>> Setter<T> for Unicode {
     }
 }
 
+impl Setter<str> for Unicode {
+    #[inline(always)]
+    fn write_clipboard(&self, data: &str) -> SysResult<()> {
+        crate::raw::set_string(data)
+    }
+}
+
 impl From<&Unicode> for u32 {
     #[inline(always)]
     fn from(_: &Unicode) -> Self {
@@ -164,12 +584,110 @@ impl From<&Unicode> for u32 {
     }
 }
 
+#[derive(Copy, Clone)]
+///Format to read/write unicode string as UTF-16, avoiding conversion to/from UTF-8.
+///
+///`Getter` copies `CF_UNICODETEXT` up to null terminator, while `Setter` appends terminator if it is missing.
+///Refer to [get_string_wide](../raw/fn.get_string_wide.html) and [set_string_wide](../raw/fn.set_string_wide.html)
+pub struct UnicodeWide;
+
+impl Getter<alloc::vec::Vec<u16>> for UnicodeWide {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::vec::Vec<u16>) -> SysResult<usize> {
+        crate::raw::get_string_wide(out)
+    }
+}
+
+impl Setter<[u16]> for UnicodeWide {
+    #[inline(always)]
+    fn write_clipboard(&self, data: &[u16]) -> SysResult<()> {
+        crate::raw::set_string_wide(data)
+    }
+}
+
+impl Setter<alloc::vec::Vec<u16>> for UnicodeWide {
+    #[inline(always)]
+    fn write_clipboard(&self, data: &alloc::vec::Vec<u16>) -> SysResult<()> {
+        crate::raw::set_string_wide(data)
+    }
+}
+
+impl From<&UnicodeWide> for u32 {
+    #[inline(always)]
+    fn from(_: &UnicodeWide) -> Self {
+        CF_UNICODETEXT
+    }
+}
+
+#[derive(Copy, Clone)]
+///Format to read/write ANSI text i.e. `CF_TEXT`, converting it from/to UTF-8.
+///
+///Unlike reading `CF_TEXT` via [RawData](struct.RawData.html), terminating null character is removed.
+///
+///`Getter` decodes text as described in [get_ansi_string](../raw/fn.get_ansi_string.html).
+///`Setter` encodes text using code page of its locale, which is written as `CF_LOCALE` together with text.
+pub struct AnsiText {
+    lcid: u32,
+}
+
+impl AnsiText {
+    #[inline(always)]
+    ///Creates new instance, using current user locale.
+    pub const fn new() -> Self {
+        Self::with_locale(crate::raw::LOCALE_USER_DEFAULT)
+    }
+
+    #[inline(always)]
+    ///Creates new instance, using specified locale identifier (`LCID`) when writing text.
+    pub const fn with_locale(lcid: u32) -> Self {
+        Self {
+            lcid
+        }
+    }
+
+    #[inline(always)]
+    ///Returns locale identifier used when writing text.
+    pub const fn locale(&self) -> u32 {
+        self.lcid
+    }
+}
+
+impl Default for AnsiText {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Getter<alloc::string::String> for AnsiText {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::string::String) -> SysResult<usize> {
+        crate::raw::get_ansi_string(out)
+    }
+}
+
+impl<T: AsRef<str>> Setter<T> for AnsiText {
+    #[inline(always)]
+    fn write_clipboard(&self, data: &T) -> SysResult<()> {
+        crate::raw::set_ansi_string(data.as_ref(), self.lcid)
+    }
+}
+
+impl From<&AnsiText> for u32 {
+    #[inline(always)]
+    fn from(_: &AnsiText) -> Self {
+        CF_TEXT
+    }
+}
+
 #[derive(Copy, Clone)]
 ///Format for file lists (generated by drag & drop).
 ///
 ///Corresponds to `CF_HDROP`
 ///
-///`read_clipboard` returns number of file names
+///`read_clipboard` returns number of file names. Use [FileListInfo](struct.FileListInfo.html) to get drop point too
+///
+///To write arbitrary paths use [set_file_list_path](../raw/fn.set_file_list_path.html)
 pub struct FileList;
 
 impl Getter<alloc::vec::Vec<alloc::string::String>> for FileList {
@@ -187,6 +705,28 @@ impl Getter<alloc::vec::Vec<std::path::PathBuf>> for FileList {
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+///File list together with information about where files were dropped.
+pub struct FileListInfo {
+    ///File names.
+    pub files: alloc::vec::Vec<alloc::string::String>,
+    ///Drop point, which is `(0, 0)` if not specified.
+    pub point: (i32, i32),
+    ///Whether drop point is in non-client area of window, in which case it is in screen coordinates.
+    pub is_non_client: bool,
+}
+
+impl Getter<FileListInfo> for FileList {
+    ///Appends file names to `out.files`, while overwriting drop point information.
+    fn read_clipboard(&self, out: &mut FileListInfo) -> SysResult<usize> {
+        let (point, is_non_client) = crate::raw::get_file_list_drop_point()?;
+        let result = crate::raw::get_file_list(&mut out.files)?;
+        out.point = point;
+        out.is_non_client = is_non_client;
+        Ok(result)
+    }
+}
+
 impl<T: AsRef<str>> Setter<[T]> for FileList {
     #[inline(always)]
     fn write_clipboard(&self, data: &[T]) -> SysResult<()> {
@@ -207,6 +747,17 @@ impl From<&FileList> for u32 {
 ///Both `Getter` and `Setter` expects image as header and rgb payload
 pub struct Bitmap;
 
+impl Bitmap {
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    ///Reads image as BMP file, writing it into `out`.
+    ///
+    ///Refer to [get_bitmap_to](../raw/fn.get_bitmap_to.html) for details
+    pub fn read_clipboard_to<W: std::io::Write>(&self, out: W) -> SysResult<usize> {
+        crate::raw::get_bitmap_to(out)
+    }
+}
+
 impl Getter<alloc::vec::Vec<u8>> for Bitmap {
     #[inline(always)]
     fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
@@ -228,6 +779,78 @@ impl From<&Bitmap> for u32 {
     }
 }
 
+#[derive(Copy, Clone)]
+///Format for device independent bitmap i.e. `CF_DIB`.
+///
+///`Getter` reads DIB as it is stored on clipboard, which is BMP file without file header.
+pub struct Dib;
+
+impl Dib {
+    ///Reads DIB as [Image](../dib/struct.Image.html)
+    pub fn read_image(&self) -> SysResult<crate::dib::Image> {
+        let mut data = alloc::vec::Vec::new();
+        crate::raw::get_vec(CF_DIB, &mut data)?;
+        crate::dib::Image::new(data).ok_or_else(crate::utils::invalid_data)
+    }
+}
+
+impl Getter<alloc::vec::Vec<u8>> for Dib {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+        crate::raw::get_vec(CF_DIB, out)
+    }
+}
+
+impl From<&Dib> for u32 {
+    #[inline(always)]
+    fn from(_: &Dib) -> Self {
+        CF_DIB
+    }
+}
+
+#[derive(Copy, Clone)]
+///Format for device independent bitmap with `BITMAPV5HEADER` i.e. `CF_DIBV5`.
+///
+///Unlike `CF_DIB`, header specifies alpha mask, allowing to preserve transparency.
+///
+///`Getter` reads DIB as it is stored on clipboard, which is BMP file without file header.
+///`Setter` accepts only images with `BITMAPV5HEADER`, such as created by [Image::from_bgra](../dib/struct.Image.html#method.from_bgra)
+pub struct DibV5;
+
+impl DibV5 {
+    ///Reads DIB as [Image](../dib/struct.Image.html)
+    pub fn read_image(&self) -> SysResult<crate::dib::Image> {
+        let mut data = alloc::vec::Vec::new();
+        crate::raw::get_vec(CF_DIBV5, &mut data)?;
+        crate::dib::Image::new(data).ok_or_else(crate::utils::invalid_data)
+    }
+}
+
+impl Getter<alloc::vec::Vec<u8>> for DibV5 {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+        crate::raw::get_vec(CF_DIBV5, out)
+    }
+}
+
+impl Setter<crate::dib::Image> for DibV5 {
+    #[inline]
+    fn write_clipboard(&self, data: &crate::dib::Image) -> SysResult<()> {
+        if (data.header().biSize as usize) < crate::dib::V5_HEADER_LEN {
+            return Err(crate::utils::invalid_data());
+        }
+
+        crate::raw::set(CF_DIBV5, data.as_bytes())
+    }
+}
+
+impl From<&DibV5> for u32 {
+    #[inline(always)]
+    fn from(_: &DibV5) -> Self {
+        CF_DIBV5
+    }
+}
+
 #[derive(Copy, Clone)]
 ///HTML Foramt
 ///
@@ -280,4 +903,382 @@ impl From<&Html> for u32 {
     }
 }
 
-impl_format!(Html, Bitmap, RawData, Unicode, FileList);
+#[derive(Copy, Clone)]
+///Rich Text Format
+///
+///`Getter<Vec<u8>>` and `Setter` operate on raw RTF bytes, which are copied as they are.
+///RTF is ASCII text, with non-ASCII characters escaped or encoded using code page declared in its header.
+///Hence `Getter<String>` decodes it as [read_decoded](#method.read_decoded) does.
+///
+///Format is registered only once, after which its identifier is reused.
+pub struct Rtf(NonZeroU32);
+
+impl Rtf {
+    #[inline(always)]
+    ///Creates new instance, if possible
+    pub fn new() -> Option<Self> {
+        static RTF: LazyFormat = LazyFormat::new("Rich Text Format");
+        RTF.get().map(Self)
+    }
+
+    #[inline(always)]
+    ///Gets raw format code
+    pub fn code(&self) -> u32 {
+        self.0.get()
+    }
+
+    #[inline(always)]
+    ///Reads RTF document, decoding it according to code page declared in its header.
+    ///
+    ///Refer to [get_rtf](../raw/fn.get_rtf.html) for details
+    pub fn read_decoded(&self, out: &mut alloc::string::String) -> SysResult<usize> {
+        crate::raw::get_rtf(self.code(), out)
+    }
+}
+
+impl Getter<alloc::vec::Vec<u8>> for Rtf {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+        crate::raw::get_vec(self.code(), out)
+    }
+}
+
+impl Getter<alloc::string::String> for Rtf {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::string::String) -> SysResult<usize> {
+        self.read_decoded(out)
+    }
+}
+
+impl<T: AsRef<[u8]>> Setter<T> for Rtf {
+    #[inline(always)]
+    fn write_clipboard(&self, data: &T) -> SysResult<()> {
+        crate::raw::set(self.code(), data.as_ref())
+    }
+}
+
+impl From<&Rtf> for u32 {
+    #[inline(always)]
+    fn from(value: &Rtf) -> Self {
+        value.code()
+    }
+}
+
+#[derive(Copy, Clone)]
+///Format for hyperlinks, put by browsers when link is copied or dragged.
+///
+///Corresponds to registered format `UniformResourceLocatorW`, which stores URL as null terminated UTF-16 string.
+pub struct Url(NonZeroU32);
+
+impl Url {
+    #[inline(always)]
+    ///Creates new instance, if possible
+    pub fn new() -> Option<Self> {
+        //utf-16 "UniformResourceLocatorW"
+        const NAME: [u16; 24] = [85, 110, 105, 102, 111, 114, 109, 82, 101, 115, 111, 117, 114, 99, 101, 76, 111, 99, 97, 116, 111, 114, 87, 0];
+        unsafe {
+            crate::raw::register_raw_format(&NAME).map(Self)
+        }
+    }
+
+    #[inline(always)]
+    ///Gets raw format code
+    pub fn code(&self) -> u32 {
+        self.0.get()
+    }
+}
+
+impl Getter<alloc::vec::Vec<u8>> for Url {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+        crate::raw::get_wide_string(self.code(), out)
+    }
+}
+
+impl Getter<alloc::string::String> for Url {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::string::String) -> SysResult<usize> {
+        self.read_clipboard(unsafe { out.as_mut_vec() })
+    }
+}
+
+impl<T: AsRef<str>> Setter<T> for Url {
+    #[inline(always)]
+    fn write_clipboard(&self, data: &T) -> SysResult<()> {
+        crate::raw::set_wide_string(self.code(), data.as_ref())
+    }
+}
+
+impl From<&Url> for u32 {
+    #[inline(always)]
+    fn from(value: &Url) -> Self {
+        value.code()
+    }
+}
+
+#[derive(Copy, Clone)]
+///Format for GIF images, including animated ones, as raw bytes.
+///
+///Corresponds to registered format `GIF`, which support varies by application.
+pub struct Gif(NonZeroU32);
+
+impl Gif {
+    #[inline(always)]
+    ///Creates new instance, if possible
+    pub fn new() -> Option<Self> {
+        //utf-16 "GIF"
+        const NAME: [u16; 4] = [71, 73, 70, 0];
+        unsafe {
+            crate::raw::register_raw_format(&NAME).map(Self)
+        }
+    }
+
+    #[inline(always)]
+    ///Gets raw format code
+    pub fn code(&self) -> u32 {
+        self.0.get()
+    }
+}
+
+impl Getter<alloc::vec::Vec<u8>> for Gif {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+        crate::raw::get_vec(self.code(), out)
+    }
+}
+
+impl<T: AsRef<[u8]>> Setter<T> for Gif {
+    #[inline(always)]
+    fn write_clipboard(&self, data: &T) -> SysResult<()> {
+        crate::raw::set(self.code(), data.as_ref())
+    }
+}
+
+impl From<&Gif> for u32 {
+    #[inline(always)]
+    fn from(value: &Gif) -> Self {
+        value.code()
+    }
+}
+
+#[derive(Copy, Clone)]
+///Format for PNG images as raw bytes.
+///
+///Corresponds to registered format `PNG`, which is used by browsers and Office applications.
+///Format is registered only once, after which its identifier is reused.
+pub struct Png(NonZeroU32);
+
+impl Png {
+    #[inline(always)]
+    ///Creates new instance, if possible
+    pub fn new() -> Option<Self> {
+        static PNG: LazyFormat = LazyFormat::new("PNG");
+        PNG.get().map(Self)
+    }
+
+    #[inline(always)]
+    ///Gets raw format code
+    pub fn code(&self) -> u32 {
+        self.0.get()
+    }
+}
+
+impl Getter<alloc::vec::Vec<u8>> for Png {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+        crate::raw::get_vec(self.code(), out)
+    }
+}
+
+impl<T: AsRef<[u8]>> Setter<T> for Png {
+    #[inline(always)]
+    fn write_clipboard(&self, data: &T) -> SysResult<()> {
+        crate::raw::set(self.code(), data.as_ref())
+    }
+}
+
+impl From<&Png> for u32 {
+    #[inline(always)]
+    fn from(value: &Png) -> Self {
+        value.code()
+    }
+}
+
+#[derive(Copy, Clone)]
+///Format for audio as raw bytes of WAV file, i.e. `CF_WAVE`.
+///
+///Data is copied as it is, without any null character handling.
+pub struct Wave;
+
+impl Getter<alloc::vec::Vec<u8>> for Wave {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+        crate::raw::get_vec(CF_WAVE, out)
+    }
+}
+
+impl<T: AsRef<[u8]>> Setter<T> for Wave {
+    #[inline(always)]
+    fn write_clipboard(&self, data: &T) -> SysResult<()> {
+        crate::raw::set(CF_WAVE, data.as_ref())
+    }
+}
+
+impl From<&Wave> for u32 {
+    #[inline(always)]
+    fn from(_: &Wave) -> Self {
+        CF_WAVE
+    }
+}
+
+//Reads ASCII text of `format`, appending it to `out` without terminating null character.
+fn read_ascii(format: u32, out: &mut alloc::string::String) -> SysResult<usize> {
+    let out = unsafe { out.as_mut_vec() };
+    let cursor = out.len();
+    crate::raw::get_vec(format, out)?;
+
+    if let Some(null_idx) = out[cursor..].iter().position(|byte| *byte == 0) {
+        out.truncate(cursor + null_idx);
+    }
+
+    if !out[cursor..].is_ascii() {
+        out.truncate(cursor);
+        return Err(crate::utils::invalid_data());
+    }
+
+    Ok(out.len() - cursor)
+}
+
+#[derive(Copy, Clone)]
+///Format for spreadsheet data in Data Interchange Format, i.e. `CF_DIF`.
+///
+///`Getter` reads ASCII text without terminating null character, failing with `ERROR_INVALID_DATA` on non-ASCII data.
+pub struct Dif;
+
+impl Getter<alloc::string::String> for Dif {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::string::String) -> SysResult<usize> {
+        read_ascii(CF_DIF, out)
+    }
+}
+
+impl From<&Dif> for u32 {
+    #[inline(always)]
+    fn from(_: &Dif) -> Self {
+        CF_DIF
+    }
+}
+
+#[derive(Copy, Clone)]
+///Format for spreadsheet data in Microsoft Symbolic Link format, i.e. `CF_SYLK`.
+///
+///`Getter` reads ASCII text without terminating null character, failing with `ERROR_INVALID_DATA` on non-ASCII data.
+pub struct Sylk;
+
+impl Getter<alloc::string::String> for Sylk {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::string::String) -> SysResult<usize> {
+        read_ascii(CF_SYLK, out)
+    }
+}
+
+impl From<&Sylk> for u32 {
+    #[inline(always)]
+    fn from(_: &Sylk) -> Self {
+        CF_SYLK
+    }
+}
+
+impl_format!(Html, Rtf, Url, Gif, Png, Wave, Dif, Sylk, Bitmap, Dib, DibV5, RawData, Unicode, UnicodeWide, AnsiText, FileList);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Category of clipboard format.
+pub enum FormatCategory {
+    ///Textual data.
+    Text,
+    ///Image data.
+    Image,
+    ///Files.
+    File,
+    ///Anything else.
+    Other,
+}
+
+///Determines category of `format`.
+///
+///Standard formats are categorized as following:
+///
+///- Text: `CF_TEXT`, `CF_OEMTEXT`, `CF_UNICODETEXT`, `CF_DSPTEXT`;
+///- Image: `CF_BITMAP`, `CF_DIB`, `CF_DIBV5`, `CF_TIFF`, `CF_METAFILEPICT`, `CF_ENHMETAFILE` and their display variants;
+///- File: `CF_HDROP`.
+///
+///Registered formats are categorized by case insensitive name lookup, in following order:
+///
+///- Text: name contains `text`, `html` or `rtf`;
+///- Image: name contains `png`, `jpeg`, `jpg`, `gif`, `image` or `bitmap`;
+///- File: name contains `file` or `shell idlist`.
+///
+///Everything else is `Other`.
+pub fn category(format: c_uint) -> FormatCategory {
+    const TEXT: &[&str] = &["text", "html", "rtf"];
+    const IMAGE: &[&str] = &["png", "jpeg", "jpg", "gif", "image", "bitmap"];
+    const FILE: &[&str] = &["file", "shell idlist"];
+
+    match format {
+        CF_TEXT | CF_OEMTEXT | CF_UNICODETEXT | CF_DSPTEXT => FormatCategory::Text,
+        CF_BITMAP | CF_DIB | CF_DIBV5 | CF_TIFF | CF_METAFILEPICT | CF_ENHMETAFILE | CF_DSPBITMAP | CF_DSPMETAFILEPICT | CF_DSPENHMETAFILE => FormatCategory::Image,
+        CF_HDROP => FormatCategory::File,
+        0xC000..=0xFFFF => {
+            let name = match crate::raw::format_name_big(format) {
+                Some(name) => name.to_ascii_lowercase(),
+                None => return FormatCategory::Other,
+            };
+            let is_any = |keywords: &[&str]| keywords.iter().any(|keyword| name.contains(keyword));
+
+            if is_any(TEXT) {
+                FormatCategory::Text
+            } else if is_any(IMAGE) {
+                FormatCategory::Image
+            } else if is_any(FILE) {
+                FormatCategory::File
+            } else {
+                FormatCategory::Other
+            }
+        },
+        _ => FormatCategory::Other,
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+///Clipboard formats grouped by [category](fn.category.html).
+pub struct CategorizedFormats {
+    ///Text formats.
+    pub text: alloc::vec::Vec<c_uint>,
+    ///Image formats.
+    pub image: alloc::vec::Vec<c_uint>,
+    ///File formats.
+    pub file: alloc::vec::Vec<c_uint>,
+    ///Other formats.
+    pub other: alloc::vec::Vec<c_uint>,
+}
+
+impl CategorizedFormats {
+    ///Groups `formats`, preserving their order within each category.
+    pub fn new<I: IntoIterator<Item = c_uint>>(formats: I) -> Self {
+        let mut result = Self::default();
+
+        for format in formats {
+            match category(format) {
+                FormatCategory::Text => result.text.push(format),
+                FormatCategory::Image => result.image.push(format),
+                FormatCategory::File => result.file.push(format),
+                FormatCategory::Other => result.other.push(format),
+            }
+        }
+
+        result
+    }
+}
+
+pub mod office;
+pub mod shell;
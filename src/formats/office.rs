@@ -0,0 +1,102 @@
+//! Microsoft Office specific formats.
+//!
+//! Office applications put shapes, SmartArt and other drawing objects onto clipboard under
+//! registered formats, which are identified by name only.
+//!
+//! Content of these formats is not documented, therefore it is treated as opaque bytes that can
+//! be read from one document and written back to be pasted into another.
+
+use crate::{SysResult, Getter, Setter};
+use super::Format;
+use crate::raw::{self, EnumFormats};
+
+use core::num::NonZeroU32;
+
+///Office Graphics (shapes, SmartArt, charts).
+pub const ART_GVML: &str = "Art::GVML ClipFormat";
+///Office text inside drawing object.
+pub const ART_TEXT: &str = "Art::Text ClipFormat";
+///Legacy Office drawing shapes.
+pub const DRAWING_SHAPE: &str = "Office Drawing Shape Format";
+///PowerPoint internal representation of shapes.
+pub const POWERPOINT_SHAPES: &str = "PowerPoint 12.0 Internal Shapes";
+///OLE embedded object.
+pub const EMBED_SOURCE: &str = "Embed Source";
+///OLE object descriptor.
+pub const OBJECT_DESCRIPTOR: &str = "Object Descriptor";
+
+///List of known Office format names.
+pub const KNOWN_FORMATS: &[&str] = &[
+    ART_GVML,
+    ART_TEXT,
+    DRAWING_SHAPE,
+    POWERPOINT_SHAPES,
+    EMBED_SOURCE,
+    OBJECT_DESCRIPTOR,
+];
+
+///Returns known Office format name, if `format` is one of [KNOWN_FORMATS](constant.KNOWN_FORMATS.html).
+pub fn name_of(format: u32) -> Option<&'static str> {
+    //All known names are ASCII and fit into this buffer
+    let mut buf = [0u8; 64];
+    let name = raw::format_name(format, buf.as_mut_slice().into())?;
+    KNOWN_FORMATS.iter().find(|known| **known == name).copied()
+}
+
+///Returns iterator over available Office formats as pairs of format code and name.
+///
+///# Pre-conditions:
+///
+///* [open()](../../raw/fn.open.html) has been called.
+pub fn enum_avail() -> impl Iterator<Item = (u32, &'static str)> {
+    EnumFormats::new().filter_map(|format| name_of(format).map(|name| (format, name)))
+}
+
+#[derive(Copy, Clone)]
+///Office format, identified by name.
+///
+///Reads and writes content as raw bytes without any transformation.
+pub struct Office(NonZeroU32);
+
+impl Office {
+    #[inline(always)]
+    ///Creates new instance by registering format `name`, if possible.
+    pub fn new(name: &str) -> Option<Self> {
+        raw::register_format(name).map(Self)
+    }
+
+    #[inline(always)]
+    ///Creates instance of `Art::GVML ClipFormat`, if possible.
+    pub fn gvml() -> Option<Self> {
+        Self::new(ART_GVML)
+    }
+
+    #[inline(always)]
+    ///Gets raw format code
+    pub fn code(&self) -> u32 {
+        self.0.get()
+    }
+}
+
+impl Getter<alloc::vec::Vec<u8>> for Office {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+        raw::get_vec(self.code(), out)
+    }
+}
+
+impl<T: AsRef<[u8]>> Setter<T> for Office {
+    #[inline(always)]
+    fn write_clipboard(&self, data: &T) -> SysResult<()> {
+        raw::set(self.code(), data.as_ref())
+    }
+}
+
+impl From<&Office> for u32 {
+    #[inline(always)]
+    fn from(value: &Office) -> Self {
+        value.code()
+    }
+}
+
+impl_format!(Office);
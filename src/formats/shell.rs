@@ -0,0 +1,132 @@
+//! Shell specific formats.
+//!
+//! Explorer puts `Shell IDList Array` onto clipboard in addition to `CF_HDROP`, which allows to
+//! transfer items of virtual folders that have no file system path.
+
+use crate::{SysResult, Getter};
+use crate::raw;
+use super::Format;
+
+use core::num::NonZeroU32;
+use core::mem;
+
+///Name of shell items format.
+pub const SHELL_IDLIST_ARRAY: &str = "Shell IDList Array";
+
+#[derive(Copy, Clone)]
+///Format for `Shell IDList Array`.
+///
+///`Getter` reads content as raw bytes that can be parsed with [Cida](struct.Cida.html).
+pub struct ShellIdList(NonZeroU32);
+
+impl ShellIdList {
+    #[inline(always)]
+    ///Creates new instance, if possible
+    pub fn new() -> Option<Self> {
+        raw::register_format(SHELL_IDLIST_ARRAY).map(Self)
+    }
+
+    #[inline(always)]
+    ///Gets raw format code
+    pub fn code(&self) -> u32 {
+        self.0.get()
+    }
+}
+
+impl Getter<alloc::vec::Vec<u8>> for ShellIdList {
+    #[inline(always)]
+    fn read_clipboard(&self, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+        raw::get_vec(self.code(), out)
+    }
+}
+
+impl From<&ShellIdList> for u32 {
+    #[inline(always)]
+    fn from(value: &ShellIdList) -> Self {
+        value.code()
+    }
+}
+
+impl_format!(ShellIdList);
+
+#[inline(always)]
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(mem::size_of::<u32>())?)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+#[derive(Copy, Clone)]
+///`CIDA` structure, describing parent folder and its items as `PIDL`s.
+///
+///Each `PIDL` is exposed as raw bytes of `ITEMIDLIST`, including its terminator.
+pub struct Cida<'a> {
+    data: &'a [u8],
+    len: usize,
+}
+
+impl<'a> Cida<'a> {
+    ///Parses `CIDA` structure, returning `None` if it is malformed.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let len = read_u32(data, 0)? as usize;
+        let this = Self {
+            data,
+            len,
+        };
+
+        //Parent folder and items
+        for idx in 0..=len {
+            this.pidl(idx)?;
+        }
+
+        Some(this)
+    }
+
+    #[inline(always)]
+    ///Returns number of items, excluding parent folder.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    ///Returns whether there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    ///Returns `PIDL` of parent folder.
+    pub fn parent(&self) -> &'a [u8] {
+        //Validated on parse
+        self.pidl(0).unwrap_or_default()
+    }
+
+    #[inline(always)]
+    ///Returns `PIDL` of item at `idx`, relative to parent folder.
+    pub fn get(&self, idx: usize) -> Option<&'a [u8]> {
+        if idx < self.len {
+            self.pidl(idx + 1)
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    ///Returns iterator over items.
+    pub fn iter(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.len).filter_map(move |idx| self.get(idx))
+    }
+
+    fn pidl(&self, idx: usize) -> Option<&'a [u8]> {
+        let start = read_u32(self.data, mem::size_of::<u32>() * (idx + 1))? as usize;
+
+        //ITEMIDLIST is sequence of SHITEMID, prefixed with its size, ending with zero size
+        let mut cursor = start;
+        loop {
+            let size = self.data.get(cursor..cursor.checked_add(2)?)?;
+            match u16::from_le_bytes([size[0], size[1]]) as usize {
+                0 => break self.data.get(start..cursor + 2),
+                size => cursor = cursor.checked_add(size)?,
+            }
+        }
+    }
+}
@@ -8,9 +8,18 @@ pub const START_FRAGMENT: &str = "StartFragment";
 pub const END_FRAGMENT: &str = "EndFragment";
 pub const START_HTML: &str = "StartHTML";
 pub const END_HTML: &str = "EndHTML";
+pub const FRAGMENT_START_MARKER: &str = "<!--StartFragment-->";
+pub const FRAGMENT_END_MARKER: &str = "<!--EndFragment-->";
 pub const BODY_HEADER: &str = "<html>\r\n<body>\r\n<!--StartFragment-->";
 pub const BODY_FOOTER: &str = "<!--EndFragment-->\r\n</body>\r\n</html>";
 
+///Locates fragment using comment markers, returning its start and end offsets.
+pub fn find_fragment(data: &str) -> Option<(usize, usize)> {
+    let start = data.find(FRAGMENT_START_MARKER)? + FRAGMENT_START_MARKER.len();
+    let end = start + data[start..].find(FRAGMENT_END_MARKER)?;
+    Some((start, end))
+}
+
 pub struct LengthBuffer([u8; LEN_SIZE]);
 
 impl LengthBuffer {
@@ -11,6 +11,7 @@
 //!
 //! - `std` - Enables usage of `std`, including `std::error::Error` trait.
 //! - `monitor` - Enables code related to clipboard monitoring.
+//! - `tokio` - Enables `AsyncMonitor`, which allows to await clipboard changes. Implies `monitor` and `std`.
 //!
 //!# Clipboard
 //!
@@ -24,8 +25,18 @@
 //!
 //! - [RawData](formats/struct.RawData.html) - Reads raw bytes from specified format.
 //! - [Unicode](formats/struct.Unicode.html) - Reads unicode string from clipboard.
+//! - [AnsiText](formats/struct.AnsiText.html) - Reads ANSI text from clipboard, decoding it to UTF-8.
 //! - [Bitmap](formats/struct.Bitmap.html) - Reads RGB data of image on clipboard.
+//! - [Dib](formats/struct.Dib.html) - Reads device independent bitmap from clipboard.
+//! - [DibV5](formats/struct.DibV5.html) - Reads device independent bitmap with alpha channel from clipboard.
 //! - [FileList](formats/struct.FileList.html) - Reads list of files from clipboard.
+//! - [Rtf](formats/struct.Rtf.html) - Reads Rich Text as raw bytes or decoded string.
+//! - [Url](formats/struct.Url.html) - Reads URL of copied hyperlink.
+//! - [Gif](formats/struct.Gif.html) - Reads GIF image as raw bytes.
+//! - [Png](formats/struct.Png.html) - Reads PNG image as raw bytes.
+//! - [Wave](formats/struct.Wave.html) - Reads audio as raw bytes of WAV file.
+//! - [Dif](formats/struct.Dif.html) and [Sylk](formats/struct.Sylk.html) - Reads spreadsheet data as ASCII text.
+//! - [Office](formats/office/struct.Office.html) - Reads Office specific formats as raw bytes.
 //!
 //! Depending on format, getter can extract data into various data types.
 //!
@@ -35,7 +46,15 @@
 //!
 //! - [RawData](formats/struct.RawData.html) - Writes raw bytes onto specified format.
 //! - [Unicode](formats/struct.Unicode.html) - Writes unicode string onto clipboard.
+//! - [AnsiText](formats/struct.AnsiText.html) - Writes ANSI text onto clipboard, encoding it with user locale.
 //! - [Bitmap](formats/struct.Bitmap.html) - Writes RGB data of image on clipboard.
+//! - [DibV5](formats/struct.DibV5.html) - Writes device independent bitmap with alpha channel.
+//! - [Rtf](formats/struct.Rtf.html) - Writes Rich Text as raw bytes.
+//! - [Url](formats/struct.Url.html) - Writes URL of hyperlink.
+//! - [Gif](formats/struct.Gif.html) - Writes GIF image as raw bytes.
+//! - [Png](formats/struct.Png.html) - Writes PNG image as raw bytes.
+//! - [Wave](formats/struct.Wave.html) - Writes audio as raw bytes of WAV file.
+//! - [Office](formats/office/struct.Office.html) - Writes Office specific formats as raw bytes.
 //!
 //! Default setters are generic over type allowing anything that can be referenced as byte slice or
 //! `str`
@@ -64,6 +83,10 @@
 //!assert_eq!(formats::Unicode.read_clipboard(&mut output).expect("Read sample"), SAMPLE.len());
 //!assert_eq!(format!("{0}{0}", SAMPLE), output);
 //!
+//!//Or replace content, re-using buffer capacity
+//!assert_eq!(formats::Unicode.load(&mut output).expect("Load sample"), SAMPLE.len());
+//!assert_eq!(output, SAMPLE);
+//!
 //!```
 //!
 //!## Simplified API
@@ -81,7 +104,7 @@
 
 #![no_std]
 #![warn(missing_docs)]
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::style))]
+#![allow(clippy::style)]
 
 #[cfg(feature = "std")]
 extern crate std;
@@ -95,24 +118,65 @@ pub mod formats;
 pub use formats::Format;
 mod html;
 pub mod raw;
+pub mod dib;
+pub mod snapshot;
+pub use snapshot::{ClipboardSnapshot, ClipboardExport};
+#[cfg(feature = "std")]
+pub mod delayed;
 #[cfg(feature = "monitor")]
 pub mod monitor;
 #[cfg(feature = "monitor")]
 pub use monitor::Monitor;
+#[cfg(feature = "tokio")]
+pub use monitor::AsyncMonitor;
 pub(crate) mod utils;
 
-pub use raw::{get_owner, empty, seq_num, size, is_format_avail, register_format, count_formats, EnumFormats};
+pub use raw::{get_owner, empty, seq_num, size, is_format_avail, which_format_avail, priority_format, register_format, count_formats, EnumFormats};
 pub use formats::Unicode;
 
 pub use error_code::ErrorCode;
 ///Alias to result used by this crate
 pub type SysResult<T> = Result<T, ErrorCode>;
 
+#[cfg(feature = "std")]
+///Converts error into `std::io::Error`.
+///
+///System and POSIX errors are converted into OS error with the same code, which is the reverse of `ErrorCode` conversion from `std::io::Error`.
+pub fn to_io_error(error: ErrorCode) -> std::io::Error {
+    let category = error.category();
+    if core::ptr::eq(category, ErrorCode::new_system(0).category()) || core::ptr::eq(category, ErrorCode::new_posix(0).category()) {
+        std::io::Error::from_raw_os_error(error.raw_code())
+    } else {
+        std::io::Error::new(std::io::ErrorKind::Other, error)
+    }
+}
+
+#[cfg(feature = "std")]
+///Extension to convert [SysResult](type.SysResult.html) into `std::io::Result`.
+pub trait IntoIo<T> {
+    ///Converts error, if any, using [to_io_error](fn.to_io_error.html)
+    fn into_io(self) -> std::io::Result<T>;
+}
+
+#[cfg(feature = "std")]
+impl<T> IntoIo<T> for SysResult<T> {
+    #[inline(always)]
+    fn into_io(self) -> std::io::Result<T> {
+        self.map_err(to_io_error)
+    }
+}
+
 ///Clipboard instance, which allows to perform clipboard ops.
 ///
 ///# Note:
 ///
 ///You can have only one such instance across your program.
+///Attempt to create another one on the same thread, while instance is alive, fails with
+///[ERROR_ALREADY_INITIALIZED](raw/constant.ERROR_ALREADY_INITIALIZED.html).
+///Shortcut functions, such as [set_clipboard](fn.set_clipboard.html), re-use already open clipboard instead.
+///
+///Other threads cannot open clipboard either, but they fail with error reported by system,
+///which is [ERROR_ACCESS_DENIED](raw/constant.ERROR_ACCESS_DENIED.html).
 ///
 ///# Warning:
 ///
@@ -121,20 +185,58 @@ pub type SysResult<T> = Result<T, ErrorCode>;
 ///
 ///Therefore as soon as operations are finished, user is advised to close Clipboard.
 pub struct Clipboard {
-    _dummy: ()
+    is_cleared: core::cell::Cell<bool>,
+    owner: Option<core::ptr::NonNull<types::c_void>>,
+    //Instance that re-uses clipboard open by outer instance, hence it must not close it
+    is_nested: bool,
 }
 
+//Identifier of thread, which holds Clipboard instance, or 0.
+//As only single thread can have clipboard open, it acts as thread local flag.
+static OPEN_THREAD: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(0);
+
 impl Clipboard {
+    #[inline(always)]
+    fn opened() -> Self {
+        Self {
+            is_cleared: core::cell::Cell::new(false),
+            owner: raw::get_owner(),
+            is_nested: false,
+        }
+    }
+
+    #[inline(always)]
+    fn is_held_by_current_thread() -> bool {
+        OPEN_THREAD.load(core::sync::atomic::Ordering::Relaxed) == unsafe { sys::GetCurrentThreadId() }
+    }
+
+    //Opens clipboard, unless current thread already has instance, which is re-used then.
+    pub(crate) fn new_or_reuse(num: usize) -> SysResult<Self> {
+        match Self::is_held_by_current_thread() {
+            true => Ok(Self {
+                is_cleared: core::cell::Cell::new(false),
+                owner: raw::get_owner(),
+                is_nested: true,
+            }),
+            false => Self::new_attempts(num),
+        }
+    }
+
     #[inline(always)]
     ///Attempts to open clipboard, returning clipboard instance on success.
     pub fn new() -> SysResult<Self> {
-        raw::open().map(|_| Self { _dummy: () })
+        Self::new_for(core::ptr::null_mut())
     }
 
-    #[inline(always)]
     ///Attempts to open clipboard, associating it with specified `owner` and returning clipboard instance on success.
     pub fn new_for(owner: types::HWND) -> SysResult<Self> {
-        raw::open_for(owner).map(|_| Self { _dummy: () })
+        if Self::is_held_by_current_thread() {
+            return Err(ErrorCode::new_system(raw::ERROR_ALREADY_INITIALIZED));
+        }
+
+        raw::open_for(owner)?;
+        OPEN_THREAD.store(unsafe { sys::GetCurrentThreadId() }, core::sync::atomic::Ordering::Relaxed);
+        Ok(Self::opened())
     }
 
     #[inline(always)]
@@ -145,10 +247,531 @@ impl Clipboard {
 
     #[inline]
     ///Attempts to open clipboard, giving it `num` retries in case of failure.
-    pub fn new_attempts_for(owner: types::HWND, mut num: usize) -> SysResult<Self> {
+    pub fn new_attempts_for(owner: types::HWND, num: usize) -> SysResult<Self> {
+        Self::builder().owner(owner).attempts(num).open()
+    }
+
+    ///Attempts to open clipboard, retrying until `timeout` expires.
+    ///
+    ///Delay between retries starts at 1ms and doubles up to 64ms.
+    ///On timeout returns error of last attempt.
+    ///
+    ///If clipboard is held by calling thread, [ERROR_ALREADY_INITIALIZED](raw/constant.ERROR_ALREADY_INITIALIZED.html)
+    ///is returned immediately, as retry cannot succeed.
+    pub fn new_with_timeout(timeout: core::time::Duration) -> SysResult<Self> {
+        Self::new_retrying(timeout, |_| true)
+    }
+
+    ///Attempts to open clipboard, waiting until it is released by current holder or `max` expires.
+    ///
+    ///Unlike [new_with_timeout](#method.new_with_timeout) it retries only when clipboard is busy
+    ///(i.e. fails with [ERROR_ACCESS_DENIED](raw/constant.ERROR_ACCESS_DENIED.html)), returning any other error immediately.
+    ///On timeout returns error of last attempt.
+    ///
+    ///Clipboard held by another thread of the same process is waited for as well.
+    ///But if it is held by calling thread, waiting cannot succeed, hence
+    ///[ERROR_ALREADY_INITIALIZED](raw/constant.ERROR_ALREADY_INITIALIZED.html) is returned immediately.
+    pub fn new_wait(max: core::time::Duration) -> SysResult<Self> {
+        Self::new_retrying(max, |error| error.raw_code() == raw::ERROR_ACCESS_DENIED)
+    }
+
+    fn new_retrying<F: Fn(&ErrorCode) -> bool>(timeout: core::time::Duration, should_retry: F) -> SysResult<Self> {
+        const MAX_DELAY: u64 = 64;
+
+        let timeout = core::cmp::min(timeout.as_millis(), u64::MAX as u128) as u64;
+        let start = unsafe { sys::GetTickCount64() };
+        let mut delay = 1;
+
         loop {
-            match Self::new_for(owner) {
+            match Self::new() {
                 Ok(this) => break Ok(this),
+                Err(err) => {
+                    let elapsed = unsafe { sys::GetTickCount64() }.saturating_sub(start);
+                    if elapsed >= timeout || err.raw_code() == raw::ERROR_ALREADY_INITIALIZED || !should_retry(&err) {
+                        break Err(err);
+                    }
+
+                    unsafe { sys::Sleep(core::cmp::min(delay, timeout - elapsed) as types::DWORD) };
+                    delay = core::cmp::min(delay * 2, MAX_DELAY);
+                }
+            }
+        }
+    }
+
+    #[inline(always)]
+    ///Creates builder to configure how clipboard is opened.
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    #[inline(always)]
+    ///Empties clipboard.
+    ///
+    ///Unlike [empty](raw/fn.empty.html) it can be only invoked while clipboard is open.
+    pub fn empty(&self) -> SysResult<()> {
+        raw::empty()?;
+        self.is_cleared.set(true);
+        Ok(())
+    }
+
+    #[inline(always)]
+    ///Empties clipboard.
+    ///
+    ///Alias to [empty](#method.empty), provided for convenience.
+    pub fn clear(&self) -> SysResult<()> {
+        self.empty()
+    }
+
+    ///Returns whether clipboard has no formats available.
+    ///
+    ///Fails if number of formats cannot be determined, instead of guessing.
+    pub fn is_empty(&self) -> SysResult<bool> {
+        match raw::count_formats() {
+            Some(count) => Ok(count == 0),
+            None => Err(ErrorCode::last_system()),
+        }
+    }
+
+    #[inline(always)]
+    ///Retrieves data of `format` from clipboard.
+    pub fn get<R: Default, T: Getter<R>>(&self, format: T) -> SysResult<R> {
+        get(format)
+    }
+
+    #[inline(always)]
+    ///Sets data of `format` onto clipboard.
+    pub fn set<R: ?Sized, T: Setter<R>>(&self, format: T, data: &R) -> SysResult<()> {
+        format.write_clipboard(data)
+    }
+
+    #[cfg(feature = "std")]
+    ///Sets file `path` as `CF_HDROP` together with its `file://` URL as unicode text.
+    ///
+    ///Relative path is resolved against current directory.
+    ///
+    ///Fails with [ERROR_FILE_NOT_FOUND](raw/constant.ERROR_FILE_NOT_FOUND.html) if path doesn't exist and with `ERROR_INVALID_DATA` if path is not valid unicode.
+    pub fn set_file_and_url(&self, path: &std::path::Path) -> SysResult<()> {
+        if !path.exists() {
+            return Err(ErrorCode::new_system(raw::ERROR_FILE_NOT_FOUND));
+        }
+
+        let path = match path.is_absolute() {
+            true => path.to_path_buf(),
+            false => std::env::current_dir()?.join(path),
+        };
+        let path = match path.to_str() {
+            Some(path) => path,
+            None => return Err(utils::invalid_data()),
+        };
+
+        raw::set_file_list_with(&[path], options::DoClear)?;
+        raw::set_string_with(&utils::file_url(path), options::NoClear)
+    }
+
+    ///Computes stable hash of clipboard content, suitable to detect identical copies.
+    ///
+    ///Hash is calculated using FNV-1a over format identifier and bytes of each available format, ordered by format identifier.
+    ///
+    ///Following formats are excluded:
+    ///
+    ///- `CF_LOCALE` as it is synthesized from current keyboard layout;
+    ///- Formats that are not self-contained global memory, see [is_self_contained](formats/fn.is_self_contained.html);
+    ///- Formats which data cannot be retrieved (e.g. owner failed to render it).
+    pub fn content_hash(&self) -> SysResult<u64> {
+        let mut formats: alloc::vec::Vec<u32> = raw::EnumFormats::new().filter(|format| {
+            *format != formats::CF_LOCALE && formats::is_self_contained(*format)
+        }).collect();
+        formats.sort_unstable();
+
+        let mut hasher = utils::Fnv64::new();
+        let mut data = alloc::vec::Vec::new();
+        for format in formats {
+            data.clear();
+            if raw::get_vec(format, &mut data).is_err() {
+                continue;
+            }
+
+            hasher.write(&format.to_le_bytes());
+            hasher.write(&(data.len() as u64).to_le_bytes());
+            hasher.write(&data);
+        }
+
+        Ok(hasher.finish())
+    }
+
+    #[inline(always)]
+    ///Captures clipboard content, which can be restored later.
+    pub fn snapshot(&self) -> ClipboardSnapshot {
+        ClipboardSnapshot::capture()
+    }
+
+    ///Sets raw bytes of `format`, emptying clipboard only on first call within this session.
+    ///
+    ///Subsequent calls add `format` to the clipboard, allowing to set multiple formats at once.
+    ///Clipboard is considered emptied after [empty](#method.empty) as well.
+    pub fn set_keep(&self, format: u32, data: &[u8]) -> SysResult<()> {
+        if !self.is_cleared.get() {
+            self.empty()?;
+        }
+
+        raw::set_without_clear(format, data)
+    }
+
+    ///Empties clipboard once and sets raw bytes of each format in `items`.
+    ///
+    ///Allows to publish several representations of the same content, e.g. text, HTML and PNG.
+    ///Stops at first failure, leaving formats set so far on clipboard.
+    pub fn set_many(&self, items: &[(u32, &[u8])]) -> SysResult<()> {
+        self.empty()?;
+
+        for (format, data) in items {
+            raw::set_without_clear(*format, data)?;
+        }
+
+        Ok(())
+    }
+
+    ///Sets unicode `text`, unless clipboard owner changed since clipboard has been opened.
+    ///
+    ///Owner is checked before emptying clipboard, as emptying makes window, associated with open
+    ///clipboard, the owner. Hence emptying clipboard within this session may prevent guarded set.
+    ///
+    ///Returns `true` if text has been set, otherwise `false`.
+    pub fn set_text_guarded(&self, text: &str) -> SysResult<bool> {
+        if raw::get_owner() != self.owner {
+            return Ok(false);
+        }
+
+        raw::set_string(text)?;
+        self.is_cleared.set(true);
+        Ok(true)
+    }
+
+    #[inline(always)]
+    ///Groups available formats by category, preserving clipboard enumeration order.
+    ///
+    ///Refer to [category](formats/fn.category.html) for rules.
+    pub fn categorized_formats(&self) -> formats::CategorizedFormats {
+        formats::CategorizedFormats::new(raw::EnumFormats::new())
+    }
+
+    #[inline(always)]
+    ///Exports clipboard content with formats identified by name, suitable for transfer to another machine.
+    ///
+    ///Formats stored as handles are skipped, refer to [ClipboardExport](snapshot/struct.ClipboardExport.html).
+    pub fn export(&self) -> SysResult<ClipboardExport> {
+        Ok(ClipboardExport::capture())
+    }
+
+    ///Empties clipboard and sets content of `export`.
+    pub fn import(&self, export: &ClipboardExport) -> SysResult<()> {
+        export.apply()?;
+        self.is_cleared.set(true);
+        Ok(())
+    }
+
+    ///Picks single most representative available format.
+    ///
+    ///Formats are checked in following order:
+    ///
+    ///- `CF_HDROP`;
+    ///- `PNG`;
+    ///- `CF_DIBV5`, `CF_DIB`;
+    ///- `HTML Format`;
+    ///- `CF_UNICODETEXT`.
+    ///
+    ///Note that Windows synthesizes `CF_DIB` from `CF_BITMAP` and `CF_UNICODETEXT` from `CF_TEXT`.
+    ///If none of these formats is available, the first enumerated format is returned, which is the one owner considers the most descriptive.
+    ///
+    ///Returns `None` if clipboard is empty.
+    pub fn primary_format(&self) -> SysResult<Option<u32>> {
+        let png = formats::Png::new().map_or(0, |format| format.code());
+        let html = formats::Html::new().map_or(0, |format| format.code());
+        let priority = [formats::CF_HDROP, png, formats::CF_DIBV5, formats::CF_DIB, html, formats::CF_UNICODETEXT];
+
+        for format in priority.iter() {
+            if *format != 0 && raw::is_format_avail(*format) {
+                return Ok(Some(*format));
+            }
+        }
+
+        Ok(raw::EnumFormats::new().next())
+    }
+
+    ///Adds `CF_UNICODETEXT`, decoded from `CF_TEXT`, if clipboard content is originally ANSI text.
+    ///
+    ///Windows synthesizes `CF_UNICODETEXT` from `CF_TEXT` using `CF_LOCALE` code page only, which
+    ///garbles UTF-8 text. Synthesized formats are enumerated after the original ones, hence upgrade
+    ///happens when `CF_TEXT` is enumerated first. Text is decoded as described in [get_ansi_string](raw/fn.get_ansi_string.html).
+    ///
+    ///As clipboard has to be emptied, all formats are captured and set again using [ClipboardSnapshot](snapshot/struct.ClipboardSnapshot.html),
+    ///which means formats stored as handles are lost.
+    ///
+    ///Returns whether upgrade happened.
+    pub fn upgrade_text_to_unicode(&self) -> SysResult<bool> {
+        match raw::EnumFormats::new().find(|format| *format == formats::CF_TEXT || *format == formats::CF_UNICODETEXT) {
+            Some(formats::CF_TEXT) => (),
+            _ => return Ok(false),
+        }
+
+        let mut text = alloc::string::String::new();
+        raw::get_ansi_string(&mut text)?;
+        let snapshot = self.snapshot();
+
+        self.empty()?;
+        for (format, data) in snapshot.iter() {
+            match format {
+                formats::CF_UNICODETEXT => continue,
+                formats::CF_TEXT => raw::set_string_with(&text, options::NoClear)?,
+                _ => (),
+            }
+            raw::set_without_clear(format, data)?;
+        }
+
+        Ok(true)
+    }
+
+    #[inline(always)]
+    ///Advertises `format` for delayed rendering, keeping formats already set within this session.
+    ///
+    ///Allows to provide cheap formats immediately and render expensive ones on demand:
+    ///
+    ///- Open clipboard with owner window using [new_for](#method.new_for);
+    ///- Set immediate formats, the first of which empties clipboard, making window its owner;
+    ///- Advertise remaining formats using this method;
+    ///- Handle `WM_RENDERFORMAT` and `WM_RENDERALLFORMATS` within owner window.
+    ///
+    ///Refer to [set_delayed](raw/fn.set_delayed.html) for details.
+    pub fn set_delayed(&self, format: u32) -> SysResult<()> {
+        raw::set_delayed(format)
+    }
+
+    ///Returns hex dump of up to `max_bytes` of `format` data, suitable for bug reports.
+    ///
+    ///Each line contains offset, 16 bytes in hex and their ASCII representation.
+    ///If data is bigger than `max_bytes`, dump ends with line describing truncation.
+    pub fn hexdump_format(&self, format: u32, max_bytes: usize) -> SysResult<alloc::string::String> {
+        use core::fmt::Write;
+
+        let data = self.map(format)?;
+        let size = core::cmp::min(data.len(), max_bytes);
+
+        let mut result = alloc::string::String::new();
+        utils::hexdump(&data[..size], &mut result);
+        if size < data.len() {
+            let _ = writeln!(result, "... truncated: {} of {} bytes shown", size, data.len());
+        }
+
+        Ok(result)
+    }
+
+    #[inline(always)]
+    ///Maps data of specified `format` as read-only bytes, without copying.
+    ///
+    ///Data is accessible only while clipboard is open.
+    pub fn map(&self, format: u32) -> SysResult<raw::ClipboardMap<'_>> {
+        unsafe {
+            raw::ClipboardMap::new(format)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    ///Returns reader over data of specified `format`, allowing to stream it without copying.
+    ///
+    ///Data is accessible only while clipboard is open.
+    pub fn reader(&self, format: u32) -> SysResult<raw::ClipboardReader<'_>> {
+        unsafe {
+            raw::get_read(format)
+        }
+    }
+
+    #[inline(always)]
+    ///Sets ANSI text encoded with code page of `lcid` together with matching `CF_LOCALE`.
+    ///
+    ///Refer to [set_ansi_string](raw/fn.set_ansi_string.html) for details.
+    pub fn set_ansi_text(&self, text: &str, lcid: u32) -> SysResult<()> {
+        raw::set_ansi_string(text, lcid)?;
+        self.is_cleared.set(true);
+        Ok(())
+    }
+
+    #[inline(always)]
+    ///Sets unicode text, emptying clipboard beforehand.
+    ///
+    ///Refer to [set_string](raw/fn.set_string.html) for details.
+    pub fn set_text(&self, text: &str) -> SysResult<()> {
+        raw::set_string(text)?;
+        self.is_cleared.set(true);
+        Ok(())
+    }
+
+    #[inline(always)]
+    ///Sets raw `data` of `format`, emptying clipboard beforehand.
+    ///
+    ///Refer to [set](raw/fn.set.html) for details.
+    pub fn set_bytes(&self, format: u32, data: &[u8]) -> SysResult<()> {
+        raw::set(format, data)?;
+        self.is_cleared.set(true);
+        Ok(())
+    }
+
+    #[inline]
+    ///Retrieves unicode text as new string.
+    ///
+    ///Unlike [Getter](trait.Getter.html), which appends to existing buffer, content is always read fresh.
+    pub fn get_text(&self) -> SysResult<alloc::string::String> {
+        let mut text = alloc::string::String::new();
+        raw::get_string(unsafe { text.as_mut_vec() })?;
+        Ok(text)
+    }
+
+    #[inline]
+    ///Retrieves ANSI text, decoding it with code page of `CF_LOCALE`.
+    ///
+    ///Refer to [get_ansi_string_by_locale](raw/fn.get_ansi_string_by_locale.html) for details.
+    pub fn get_ansi_text(&self) -> SysResult<alloc::string::String> {
+        let mut text = alloc::string::String::new();
+        raw::get_ansi_string_by_locale(&mut text)?;
+        Ok(text)
+    }
+
+    #[inline]
+    ///Reads text and determines its kind, as described in [TextKind::classify](formats/enum.TextKind.html#method.classify)
+    ///
+    ///Text is read using [get_text_best_effort](fn.get_text_best_effort.html).
+    pub fn text_kind(&self) -> SysResult<formats::TextKind> {
+        get_text_best_effort().map(|text| formats::TextKind::classify(&text))
+    }
+
+    ///Reads image from clipboard, checking formats in following order:
+    ///
+    ///- `CF_DIBV5`;
+    ///- `CF_DIB`;
+    ///- `CF_BITMAP`, converted to DIB;
+    ///- `PNG`, embedded into DIB as described in [Image::from_png](dib/struct.Image.html#method.from_png).
+    ///
+    ///Returns `None` if there is no image.
+    pub fn read_image(&self) -> SysResult<Option<dib::Image>> {
+        let mut data = alloc::vec::Vec::new();
+
+        for format in [formats::CF_DIBV5, formats::CF_DIB].iter() {
+            if raw::is_format_avail(*format) {
+                raw::get_vec(*format, &mut data)?;
+                return dib::Image::new(data).ok_or_else(utils::invalid_data).map(Some);
+            }
+        }
+
+        if raw::is_format_avail(formats::CF_BITMAP) {
+            raw::get_bitmap(&mut data)?;
+            return dib::Image::from_bmp(data).ok_or_else(utils::invalid_data).map(Some);
+        }
+
+        match formats::Png::new() {
+            Some(png) if raw::is_format_avail(png.code()) => {
+                let png = self.map(png.code())?;
+                dib::Image::from_png(&png).ok_or_else(utils::invalid_data).map(Some)
+            },
+            _ => Ok(None),
+        }
+    }
+
+    #[inline]
+    ///Serializes `value` onto clipboard using its [ClipboardFormat](trait.ClipboardFormat.html).
+    ///
+    ///This function empties the clipboard before setting the data.
+    pub fn put<T: ClipboardFormat>(&self, value: &T) -> SysResult<()> {
+        raw::set(format_id_checked::<T>()?, &value.to_bytes())
+    }
+
+    #[inline]
+    ///Deserializes value from clipboard using its [ClipboardFormat](trait.ClipboardFormat.html).
+    pub fn take<T: ClipboardFormat>(&self) -> SysResult<T> {
+        let data = self.map(format_id_checked::<T>()?)?;
+        T::from_bytes(&data)
+    }
+
+    ///Compares data of `format` with `other` in place, without copying.
+    ///
+    ///As system may report size of data bigger than it was set with, trailing zero bytes of
+    ///clipboard data are not considered to be part of it.
+    pub fn format_equals(&self, format: u32, other: &[u8]) -> SysResult<bool> {
+        let data = self.map(format)?;
+        if data.len() < other.len() {
+            return Ok(false);
+        }
+
+        let (data, padding) = data.split_at(other.len());
+        Ok(data == other && padding.iter().all(|byte| *byte == 0))
+    }
+
+    #[inline]
+    ///Locks data of specified `format` once, passing it to `cb` without copying.
+    ///
+    ///Returns result of `cb` together with size of data. Data is unlocked right after `cb` returns.
+    pub fn read_scoped<R, F: FnOnce(&[u8]) -> R>(&self, format: u32, cb: F) -> SysResult<(R, usize)> {
+        let data = self.map(format)?;
+        Ok((cb(&data), data.len()))
+    }
+}
+
+///Clipboard builder, allowing to combine open options.
+///
+///By default clipboard is opened without owner and without retries.
+pub struct Builder {
+    owner: types::HWND,
+    attempts: usize,
+    delay: core::time::Duration,
+}
+
+impl Builder {
+    #[inline(always)]
+    ///Creates new instance with default options.
+    pub const fn new() -> Self {
+        Self {
+            owner: core::ptr::null_mut(),
+            attempts: 0,
+            delay: core::time::Duration::from_millis(0),
+        }
+    }
+
+    #[inline(always)]
+    ///Specifies window to associate clipboard with.
+    pub fn owner(mut self, owner: types::HWND) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    #[inline(always)]
+    ///Specifies number of retries in case of failure.
+    pub fn attempts(mut self, num: usize) -> Self {
+        self.attempts = num;
+        self
+    }
+
+    #[inline(always)]
+    ///Specifies delay between retries.
+    ///
+    ///Delay is applied with millisecond precision.
+    ///Zero delay yields remaining time in scheduler.
+    pub fn delay(mut self, delay: core::time::Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    ///Attempts to open clipboard, returning clipboard instance on success.
+    ///
+    ///Attempts are not spent if clipboard is held by calling thread, in which case
+    ///[ERROR_ALREADY_INITIALIZED](raw/constant.ERROR_ALREADY_INITIALIZED.html) is returned immediately.
+    pub fn open(self) -> SysResult<Clipboard> {
+        let delay = core::cmp::min(self.delay.as_millis(), types::DWORD::MAX as u128) as types::DWORD;
+        let mut num = self.attempts;
+
+        loop {
+            match Clipboard::new_for(self.owner) {
+                Ok(this) => break Ok(this),
+                //Held by current thread, hence retry cannot succeed
+                Err(err) if err.raw_code() == raw::ERROR_ALREADY_INITIALIZED => break Err(err),
                 Err(err) => match num {
                     0 => break Err(err),
                     _ => num -= 1,
@@ -156,14 +779,27 @@ impl Clipboard {
             }
 
             //0 causes to yield remaining time in scheduler, but remain to be scheduled once again.
-            unsafe { sys::Sleep(0) };
+            unsafe { sys::Sleep(delay) };
         }
     }
 }
 
+impl Default for Builder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for Clipboard {
     fn drop(&mut self) {
+        if self.is_nested {
+            return;
+        }
+
         let _ = raw::close();
+        //Other thread may have already opened clipboard, in which case flag is its own
+        let _ = OPEN_THREAD.compare_exchange(unsafe { sys::GetCurrentThreadId() }, 0, core::sync::atomic::Ordering::Relaxed, core::sync::atomic::Ordering::Relaxed);
     }
 }
 
@@ -172,6 +808,9 @@ impl Drop for Clipboard {
 ///Default implementations only perform write, without opening/closing clipboard
 pub trait Getter<Type> {
     ///Reads content of clipboard into `out`, returning number of bytes read on success, or otherwise 0.
+    ///
+    ///Buffer based implementations append to `out`, keeping its existing content.
+    ///Clear `out` beforehand or use replacing readers like [Unicode::load](formats/struct.Unicode.html#method.load) to get fresh content.
     fn read_clipboard(&self, out: &mut Type) -> SysResult<usize>;
 }
 
@@ -183,12 +822,74 @@ pub trait Setter<Type: ?Sized> {
     fn write_clipboard(&self, data: &Type) -> SysResult<()>;
 }
 
+///Describes type that can be stored on clipboard as bytes of particular format.
+///
+///Used by [Clipboard::put](struct.Clipboard.html#method.put) and [Clipboard::take](struct.Clipboard.html#method.take)
+///
+///Implemented for:
+///
+///- `String` as `CF_UNICODETEXT`;
+///- `Vec<u8>` as registered format `application/octet-stream`.
+pub trait ClipboardFormat: Sized {
+    ///Returns format identifier.
+    ///
+    ///Returns 0 if format cannot be obtained (e.g. its registration failed),
+    ///in which case last system error is reported.
+    fn format_id() -> u32;
+    ///Serializes value into bytes.
+    fn to_bytes(&self) -> alloc::vec::Vec<u8>;
+    ///Deserializes value from bytes.
+    fn from_bytes(bytes: &[u8]) -> SysResult<Self>;
+}
+
+impl ClipboardFormat for alloc::string::String {
+    #[inline(always)]
+    fn format_id() -> u32 {
+        formats::CF_UNICODETEXT
+    }
+
+    fn to_bytes(&self) -> alloc::vec::Vec<u8> {
+        self.encode_utf16().chain(core::iter::once(0)).flat_map(|ch| ch.to_le_bytes()).collect()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> SysResult<Self> {
+        let text = bytes.chunks_exact(2).map(|ch| u16::from_le_bytes([ch[0], ch[1]])).take_while(|ch| *ch != 0);
+        core::char::decode_utf16(text).collect::<Result<_, _>>().map_err(|_| utils::invalid_data())
+    }
+}
+
+//0 is not valid format, hence it indicates failure to obtain format identifier
+fn format_id_checked<T: ClipboardFormat>() -> SysResult<u32> {
+    match T::format_id() {
+        0 => Err(ErrorCode::last_system()),
+        format => Ok(format),
+    }
+}
+
+impl ClipboardFormat for alloc::vec::Vec<u8> {
+    #[inline]
+    fn format_id() -> u32 {
+        static OCTET_STREAM: formats::LazyFormat = formats::LazyFormat::new("application/octet-stream");
+        OCTET_STREAM.id()
+    }
+
+    #[inline(always)]
+    fn to_bytes(&self) -> alloc::vec::Vec<u8> {
+        self.clone()
+    }
+
+    #[inline(always)]
+    fn from_bytes(bytes: &[u8]) -> SysResult<Self> {
+        Ok(bytes.to_vec())
+    }
+}
+
 #[inline(always)]
 ///Runs provided callable with open clipboard, returning whether clipboard was open successfully.
 ///
 ///If clipboard fails to open, callable is not invoked.
 pub fn with_clipboard<F: FnMut()>(mut cb: F) -> SysResult<()> {
-    let _clip = Clipboard::new()?;
+    let _clip = Clipboard::new_or_reuse(0)?;
     cb();
     Ok(())
 }
@@ -199,11 +900,32 @@ pub fn with_clipboard<F: FnMut()>(mut cb: F) -> SysResult<()> {
 ///If clipboard fails to open, attempts `num` number of retries before giving up.
 ///In which case closure is not called
 pub fn with_clipboard_attempts<F: FnMut()>(num: usize, mut cb: F) -> SysResult<()> {
-    let _clip = Clipboard::new_attempts(num)?;
+    let _clip = Clipboard::new_or_reuse(num)?;
     cb();
     Ok(())
 }
 
+#[inline(always)]
+///Runs provided callable with open clipboard, returning its result.
+///
+///If clipboard fails to open, callable is not invoked.
+pub fn with_clipboard_ret<R, F: FnOnce() -> SysResult<R>>(cb: F) -> SysResult<R> {
+    let _clip = Clipboard::new_or_reuse(0)?;
+    cb()
+}
+
+#[inline]
+///Runs provided callable with open clipboard, returning its result.
+///
+///If clipboard fails to open, attempts `num` number of retries before giving up.
+///In which case closure is not called.
+///
+///Clipboard is closed once callable finishes, unless it was already open by current thread.
+pub fn with_open_clipboard<R, F: FnOnce(&Clipboard) -> SysResult<R>>(num: usize, cb: F) -> SysResult<R> {
+    let clip = Clipboard::new_or_reuse(num)?;
+    cb(&clip)
+}
+
 #[inline(always)]
 ///Retrieve data from clipboard.
 pub fn get<R: Default, T: Getter<R>>(format: T) -> SysResult<R> {
@@ -216,7 +938,7 @@ pub fn get<R: Default, T: Getter<R>>(format: T) -> SysResult<R> {
 ///
 ///It opens clipboard and gets output, if possible.
 pub fn get_clipboard<R: Default, T: Getter<R>>(format: T) -> SysResult<R> {
-    let _clip = Clipboard::new_attempts(10)?;
+    let _clip = Clipboard::new_or_reuse(10)?;
     get(format)
 }
 
@@ -231,7 +953,7 @@ pub fn set<R, T: Setter<R>>(format: T, data: R) -> SysResult<()> {
 ///
 ///It opens clipboard and attempts to set data.
 pub fn set_clipboard<R, T: Setter<R>>(format: T, data: R) -> SysResult<()> {
-    let _clip = Clipboard::new_attempts(10)?;
+    let _clip = Clipboard::new_or_reuse(10)?;
     set(format, data)
 }
 
@@ -250,3 +972,72 @@ pub fn get_clipboard_string() -> SysResult<alloc::string::String> {
 pub fn set_clipboard_string(data: &str) -> SysResult<()> {
     set_clipboard(Unicode, data)
 }
+
+///Retrieves string from clipboard, optionally trimming surrounding whitespace.
+///
+///Terminating null character is always removed.
+///
+///# Pre-conditions:
+///
+///* [open()](raw/fn.open.html) has been called.
+pub fn get_text_trimmed(trim_whitespace: bool) -> SysResult<alloc::string::String> {
+    let mut text: alloc::string::String = get(Unicode)?;
+
+    if trim_whitespace {
+        let end = text.trim_end().len();
+        text.truncate(end);
+        let start = text.len() - text.trim_start().len();
+        text.drain(..start);
+    }
+
+    Ok(text)
+}
+
+///Retrieves string from clipboard, preferring ANSI text if it was placed by owner.
+///
+///System synthesizes `CF_UNICODETEXT` from `CF_TEXT` and vice versa, but synthesized formats are
+///enumerated after formats placed by owner. Hence whichever of them comes first is original text.
+///
+///ANSI text is decoded as described in [get_ansi_string](raw/fn.get_ansi_string.html), which may be lossy.
+///
+///# Pre-conditions:
+///
+///* [open()](raw/fn.open.html) has been called.
+pub fn get_text_best_effort() -> SysResult<alloc::string::String> {
+    let mut text = alloc::string::String::new();
+
+    match raw::EnumFormats::new().find(|format| *format == formats::CF_UNICODETEXT || *format == formats::CF_TEXT) {
+        Some(formats::CF_TEXT) => raw::get_ansi_string(&mut text)?,
+        _ => Unicode.read_clipboard(&mut text)?,
+    };
+
+    Ok(text)
+}
+
+///Sets unicode text, converting lone `\n` line breaks into `\r\n` as expected by Windows applications.
+///
+///Existing `\r\n` line breaks are preserved. Use [set_string](raw/fn.set_string.html) to set text as it is.
+///
+///# Pre-conditions:
+///
+///* [open()](raw/fn.open.html) has been called.
+pub fn set_text_crlf(text: &str) -> SysResult<()> {
+    let bytes = text.as_bytes();
+    let is_lone_lf = |idx: usize| bytes[idx] == b'\n' && (idx == 0 || bytes[idx - 1] != b'\r');
+
+    let lone_lf_count = (0..bytes.len()).filter(|idx| is_lone_lf(*idx)).count();
+    if lone_lf_count == 0 {
+        return raw::set_string(text);
+    }
+
+    let mut converted = alloc::string::String::with_capacity(text.len() + lone_lf_count);
+    let mut start = 0;
+    for idx in (0..bytes.len()).filter(|idx| is_lone_lf(*idx)) {
+        converted.push_str(&text[start..idx]);
+        converted.push_str("\r\n");
+        start = idx + 1;
+    }
+    converted.push_str(&text[start..]);
+
+    raw::set_string(&converted)
+}
@@ -13,9 +13,34 @@ use windows_win::sys::{
     RemoveClipboardFormatListener,
     PostMessageW,
     WM_CLIPBOARDUPDATE,
+    WM_DRAWCLIPBOARD,
+    WM_CHANGECBCHAIN,
+};
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::types::{c_int, c_uint, WPARAM, LPARAM, LRESULT, WNDPROC};
+use crate::sys::{
+    SetClipboardViewer,
+    ChangeClipboardChain,
+    SendMessageW,
+    CallWindowProcW,
+    GetWindowLongPtrW,
+    SetWindowLongPtrW,
+    SetLastError,
 };
 
 const CLOSE_PARAM: isize = -1;
+//Retries to open clipboard after change notification
+const READ_ATTEMPTS: usize = 10;
+const READ_DELAY: core::time::Duration = core::time::Duration::from_millis(10);
+const GWLP_WNDPROC: c_int = -4;
+const GWLP_USERDATA: c_int = -21;
+//Marks viewer that is not yet part of the chain
+const VIEWER_PENDING: isize = -1;
+
+//Window procedure of the window before it becomes clipboard viewer
+static VIEWER_ORIGINAL_PROC: AtomicUsize = AtomicUsize::new(0);
 
 ///Shutdown channel
 ///
@@ -66,6 +91,90 @@ impl Drop for ClipboardListener {
     }
 }
 
+///Legacy clipboard viewer guard.
+///
+///Window procedure is replaced to handle viewer chain messages, translating `WM_DRAWCLIPBOARD`
+///into `WM_CLIPBOARDUPDATE`, while next viewer in chain is stored as window's user data.
+///
+///On drop removes window from clipboard viewer chain
+struct ClipboardViewer(HWND);
+
+unsafe extern "system" fn viewer_proc(window: HWND, msg: c_uint, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+    match msg {
+        WM_DRAWCLIPBOARD => {
+            let next = GetWindowLongPtrW(window, GWLP_USERDATA);
+            //SetClipboardViewer notifies new viewer right away, which is not actual change
+            if next == VIEWER_PENDING {
+                return 0;
+            }
+
+            PostMessageW(window, WM_CLIPBOARDUPDATE, 0, 0);
+            if next != 0 {
+                SendMessageW(next as _, msg, w_param, l_param);
+            }
+            0
+        },
+        WM_CHANGECBCHAIN => {
+            let next = GetWindowLongPtrW(window, GWLP_USERDATA);
+            if w_param as isize == next {
+                SetWindowLongPtrW(window, GWLP_USERDATA, l_param);
+            } else if next != 0 && next != VIEWER_PENDING {
+                SendMessageW(next as _, msg, w_param, l_param);
+            }
+            0
+        },
+        _ => {
+            let original = core::mem::transmute::<usize, WNDPROC>(VIEWER_ORIGINAL_PROC.load(Ordering::Relaxed));
+            CallWindowProcW(original, window, msg, w_param, l_param)
+        }
+    }
+}
+
+impl ClipboardViewer {
+    ///Adds window to clipboard viewer chain.
+    pub fn new(window: &Window) -> Result<Self, ErrorCode> {
+        let window = window.inner();
+        unsafe {
+            SetWindowLongPtrW(window, GWLP_USERDATA, VIEWER_PENDING);
+            match SetWindowLongPtrW(window, GWLP_WNDPROC, viewer_proc as *const () as isize) {
+                0 => return Err(ErrorCode::last_system()),
+                original => VIEWER_ORIGINAL_PROC.store(original as usize, Ordering::Relaxed),
+            }
+
+            //No next viewer is valid outcome, so error can be only distinguished by last error
+            SetLastError(0);
+            let next = SetClipboardViewer(window);
+            if next.is_null() {
+                let error = ErrorCode::last_system();
+                if error.raw_code() != 0 {
+                    SetWindowLongPtrW(window, GWLP_WNDPROC, VIEWER_ORIGINAL_PROC.load(Ordering::Relaxed) as isize);
+                    return Err(error);
+                }
+            }
+            SetWindowLongPtrW(window, GWLP_USERDATA, next as isize);
+        }
+
+        Ok(ClipboardViewer(window))
+    }
+}
+
+impl Drop for ClipboardViewer {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let next = GetWindowLongPtrW(self.0, GWLP_USERDATA);
+            ChangeClipboardChain(self.0, next as _);
+        }
+    }
+}
+
+//Guards are only kept for drop
+#[allow(dead_code)]
+enum Listener {
+    Format(ClipboardListener),
+    Viewer(ClipboardViewer),
+}
+
 ///Clipboard monitor
 ///
 ///This is implemented via dummy message-only window.
@@ -83,10 +192,18 @@ impl Drop for ClipboardListener {
 ///
 ///`Monitor` implements `Iterator` by continuously calling `Monitor::recv` and returning the same result.
 ///This `Iterator` is never ending, even when you perform shutdown.
+///Use `Monitor::iter` to stop iteration on shutdown.
 ///
 ///You should use `Shutdown` to interrupt blocking `Monitor::recv`
+///
+///## Backends
+///
+///- Clipboard format listener (`AddClipboardFormatListener`) is used whenever it is available, which is Windows Vista and later.
+///- Clipboard viewer chain (`SetClipboardViewer`) is used as fallback, when format listener fails to register.
+///Monitor takes care to forward chain messages to the next viewer and to leave the chain on drop.
+///Note that the chain relies on every viewer to forward messages, so misbehaving application can prevent notifications.
 pub struct Monitor {
-    _listener: ClipboardListener,
+    _listener: Listener,
     window: Window,
 }
 
@@ -95,7 +212,10 @@ impl Monitor {
     ///Creates new instance
     pub fn new() -> Result<Self, ErrorCode> {
         let window = Window::from_builder(raw::window::Builder::new().class_name("STATIC").parent_message())?;
-        let _listener = ClipboardListener::new(&window)?;
+        let _listener = match ClipboardListener::new(&window) {
+            Ok(listener) => Listener::Format(listener),
+            Err(_) => Listener::Viewer(ClipboardViewer::new(&window)?),
+        };
 
         Ok(Self {
             _listener,
@@ -104,7 +224,7 @@ impl Monitor {
     }
 
     #[inline(always)]
-    fn iter(&self) -> Messages {
+    fn messages(&self) -> Messages {
         let mut msg = Messages::new();
         msg.window(Some(self.window.inner()))
            .low(Some(WM_CLIPBOARDUPDATE))
@@ -126,7 +246,7 @@ impl Monitor {
     ///
     ///If `Shutdown` request detected, then return `Ok(false)`
     pub fn recv(&mut self) -> Result<bool, ErrorCode> {
-        if let Some(msg) = self.iter().next() {
+        if let Some(msg) = self.messages().next() {
             let msg = msg?;
             match msg.id() {
                 WM_CLIPBOARDUPDATE => return Ok(msg.inner().lParam != CLOSE_PARAM),
@@ -137,6 +257,15 @@ impl Monitor {
         unreachable!();
     }
 
+    #[inline(always)]
+    ///Creates iterator over clipboard updates, which ends once `Shutdown` request is detected.
+    pub fn iter(&mut self) -> Iter<'_> {
+        Iter {
+            monitor: self,
+            is_done: false,
+        }
+    }
+
     ///Attempts to get any clipboard update event
     ///
     ///Returns `Ok(true)` if event received,
@@ -144,7 +273,7 @@ impl Monitor {
     ///
     ///If `Shutdown` request detected, it is ignored
     pub fn try_recv(&mut self) -> Result<bool, ErrorCode> {
-        let mut iter = self.iter();
+        let mut iter = self.messages();
         iter.non_blocking();
         while let Some(msg) = iter.next() {
             let msg = msg?;
@@ -163,6 +292,18 @@ impl Monitor {
 
         Ok(false)
     }
+
+    ///Opens clipboard to read changed content, invoking `cb` with open clipboard.
+    ///
+    ///Application that changed clipboard may still hold it open when notification arrives, hence
+    ///reading right away is prone to fail. This method retries to open clipboard, waiting between
+    ///attempts, to let application finish.
+    ///
+    ///Intended to be called after `recv` or `try_recv` reported change, instead of opening clipboard directly.
+    pub fn read_on_change<R, F: FnOnce(&crate::Clipboard) -> Result<R, ErrorCode>>(&self, cb: F) -> Result<R, ErrorCode> {
+        let clipboard = crate::Clipboard::builder().attempts(READ_ATTEMPTS).delay(READ_DELAY).open()?;
+        cb(&clipboard)
+    }
 }
 
 impl Iterator for Monitor {
@@ -173,3 +314,112 @@ impl Iterator for Monitor {
         Some(self.recv())
     }
 }
+
+///Iterator over clipboard updates, created by `Monitor::iter`.
+///
+///Yields `Ok(())` on each update and ends after `Shutdown` request or error.
+pub struct Iter<'a> {
+    monitor: &'a mut Monitor,
+    is_done: bool,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Result<(), ErrorCode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+
+        match self.monitor.recv() {
+            Ok(true) => Some(Ok(())),
+            Ok(false) => {
+                self.is_done = true;
+                None
+            },
+            Err(error) => {
+                self.is_done = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+///Asynchronous clipboard monitor, based on `tokio`.
+///
+///[Monitor](struct.Monitor.html) is running on dedicated thread, sending notifications over channel,
+///which allows to await for clipboard changes without blocking executor.
+///
+///On drop monitor is shut down and its thread is joined.
+pub struct AsyncMonitor {
+    events: tokio::sync::mpsc::UnboundedReceiver<Result<(), ErrorCode>>,
+    shutdown: Option<Shutdown>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncMonitor {
+    ///Creates new instance, starting monitor thread.
+    pub fn new() -> Result<Self, ErrorCode> {
+        let (init_tx, init_rx) = std::sync::mpsc::channel();
+        let (events_tx, events) = tokio::sync::mpsc::unbounded_channel();
+
+        let thread = std::thread::spawn(move || {
+            let mut monitor = match Monitor::new() {
+                Ok(monitor) => monitor,
+                Err(error) => {
+                    let _ = init_tx.send(Err(error));
+                    return;
+                }
+            };
+            let _ = init_tx.send(Ok(monitor.shutdown_channel()));
+
+            for event in monitor.iter() {
+                if events_tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        match init_rx.recv() {
+            Ok(Ok(shutdown)) => Ok(Self {
+                events,
+                shutdown: Some(shutdown),
+                thread: Some(thread),
+            }),
+            Ok(Err(error)) => {
+                let _ = thread.join();
+                Err(error)
+            },
+            Err(_) => {
+                let _ = thread.join();
+                Err(crate::utils::unlikely_last_error())
+            }
+        }
+    }
+
+    ///Waits for next clipboard update.
+    ///
+    ///Returns `Ok(true)` if event received.
+    ///
+    ///If monitor is no longer running, then returns `Ok(false)`
+    pub async fn changed(&mut self) -> Result<bool, ErrorCode> {
+        match self.events.recv().await {
+            Some(Ok(())) => Ok(true),
+            Some(Err(error)) => Err(error),
+            None => Ok(false),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Drop for AsyncMonitor {
+    fn drop(&mut self) {
+        self.shutdown.take();
+        self.events.close();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -20,6 +20,22 @@ const BI_RGB: DWORD = 0;
 const DIB_RGB_COLORS: DWORD = 0;
 const ERROR_INCORRECT_SIZE: DWORD = 1462;
 const CP_UTF8: DWORD = 65001;
+const CP_ACP: DWORD = 0;
+///Locale identifier of current user locale.
+pub const LOCALE_USER_DEFAULT: DWORD = 0x0400;
+///Error code indicating that there is not enough memory to allocate clipboard data.
+pub const ERROR_NOT_ENOUGH_MEMORY: i32 = 8;
+///Error code indicating that [Clipboard](../struct.Clipboard.html) instance already exists.
+pub const ERROR_ALREADY_INITIALIZED: i32 = 1247;
+///Error code indicating that file is not found.
+pub const ERROR_FILE_NOT_FOUND: i32 = 2;
+///Error code indicating that clipboard is open by another thread or process.
+pub const ERROR_ACCESS_DENIED: i32 = 5;
+///Error code indicating that invalid parameter, such as empty data, is passed.
+pub const ERROR_INVALID_PARAMETER: i32 = 87;
+const MB_ERR_INVALID_CHARS: DWORD = 0x08;
+const LOCALE_IDEFAULTANSICODEPAGE: DWORD = 0x1004;
+const LOCALE_RETURN_NUMBER: DWORD = 0x20000000;
 
 use error_code::ErrorCode;
 
@@ -31,12 +47,180 @@ use alloc::borrow::ToOwned;
 use alloc::format;
 
 use crate::{SysResult, html, formats};
-use crate::utils::{unlikely_empty_size_result, RawMem};
+use crate::utils::{unlikely_empty_size_result, invalid_data, RawMem, Scope};
 
-#[cold]
-#[inline(never)]
-fn invalid_data() -> ErrorCode {
-    ErrorCode::new_system(13)
+//Conversion functions accept length as `c_int`, hence bigger inputs are converted by chunks
+const MAX_CONVERSION_CHUNK: usize = c_int::MAX as usize;
+
+///Converts UTF-8 `data` into UTF-16, writing it into `out` unless it is null.
+///
+///Returns number of UTF-16 characters written (or required if `out` is null), 0 on failure.
+///
+///When `out` is not null, it must have enough capacity to hold all characters.
+unsafe fn utf8_to_utf16(mut data: &str, mut out: *mut u16) -> usize {
+    let mut result = 0;
+
+    while !data.is_empty() {
+        let mut chunk_len = cmp::min(data.len(), MAX_CONVERSION_CHUNK);
+        while !data.is_char_boundary(chunk_len) {
+            chunk_len -= 1;
+        }
+        let (chunk, rest) = data.split_at(chunk_len);
+
+        //UTF-8 never produces more UTF-16 characters than it has bytes
+        let out_len = if out.is_null() { 0 } else { chunk_len as c_int };
+        let written = MultiByteToWideChar(CP_UTF8, 0, chunk.as_ptr(), chunk_len as c_int, out, out_len);
+        if written == 0 {
+            return 0;
+        }
+
+        result += written as usize;
+        if !out.is_null() {
+            out = out.add(written as usize);
+        }
+        data = rest;
+    }
+
+    result
+}
+
+///Converts UTF-16 `data` into UTF-8, writing it into `out` unless it is null.
+///
+///Returns number of bytes written (or required if `out` is null), 0 on failure.
+///
+///When `out` is not null, it must have enough capacity to hold all bytes.
+unsafe fn utf16_to_utf8(mut data: &[u16], mut out: *mut u8) -> usize {
+    //Single UTF-16 character produces at most 3 bytes
+    const MAX_CHUNK: usize = MAX_CONVERSION_CHUNK / 3;
+    let mut result = 0;
+
+    while !data.is_empty() {
+        let mut chunk_len = cmp::min(data.len(), MAX_CHUNK);
+        //Do not split surrogate pair
+        if chunk_len < data.len() && (0xD800..=0xDBFF).contains(&data[chunk_len - 1]) {
+            chunk_len -= 1;
+        }
+        let (chunk, rest) = data.split_at(chunk_len);
+
+        let out_len = if out.is_null() { 0 } else { (chunk_len * 3) as c_int };
+        let written = WideCharToMultiByte(CP_UTF8, 0, chunk.as_ptr(), chunk_len as c_int, out as *mut i8, out_len, ptr::null(), ptr::null_mut());
+        if written == 0 {
+            return 0;
+        }
+
+        result += written as usize;
+        if !out.is_null() {
+            out = out.add(written as usize);
+        }
+        data = rest;
+    }
+
+    result
+}
+
+///Decodes `data` encoded with `code_page`, appending it to `out`.
+///
+///Returns number of bytes appended.
+fn decode_code_page(code_page: c_uint, flags: DWORD, data: &[u8], out: &mut String) -> SysResult<usize> {
+    if data.is_empty() {
+        return Ok(0);
+    } else if data.len() > MAX_CONVERSION_CHUNK {
+        //Multi-byte code pages cannot be safely split without knowing them
+        return Err(ErrorCode::new_system(ERROR_INCORRECT_SIZE as _));
+    }
+
+    let size = unsafe {
+        MultiByteToWideChar(code_page, flags, data.as_ptr(), data.len() as c_int, ptr::null_mut(), 0)
+    };
+    if size == 0 {
+        return Err(ErrorCode::last_system());
+    }
+
+    let mut buffer = alloc::vec::Vec::<u16>::with_capacity(size as usize);
+    unsafe {
+        let size = MultiByteToWideChar(code_page, flags, data.as_ptr(), data.len() as c_int, buffer.as_mut_ptr(), size);
+        buffer.set_len(size as usize);
+
+        let out = out.as_mut_vec();
+        let storage_req_size = utf16_to_utf8(&buffer, ptr::null_mut());
+        let storage_cursor = out.len();
+        out.reserve(storage_req_size);
+        utf16_to_utf8(&buffer, out.as_mut_ptr().add(storage_cursor));
+        out.set_len(storage_cursor + storage_req_size);
+
+        Ok(storage_req_size)
+    }
+}
+
+///Encodes `data` with `code_page`, returning encoded bytes without null terminator.
+fn encode_code_page(code_page: c_uint, data: &str) -> SysResult<alloc::vec::Vec<u8>> {
+    let mut result = alloc::vec::Vec::new();
+    if data.is_empty() {
+        return Ok(result);
+    } else if data.len() > MAX_CONVERSION_CHUNK {
+        return Err(ErrorCode::new_system(ERROR_INCORRECT_SIZE as _));
+    }
+
+    let mut buffer = alloc::vec::Vec::<u16>::new();
+    unsafe {
+        let size = utf8_to_utf16(data, ptr::null_mut());
+        if size == 0 {
+            return Err(ErrorCode::last_system());
+        }
+        buffer.reserve(size);
+        utf8_to_utf16(data, buffer.as_mut_ptr());
+        buffer.set_len(size);
+
+        let size = WideCharToMultiByte(code_page, 0, buffer.as_ptr(), buffer.len() as c_int, ptr::null_mut(), 0, ptr::null(), ptr::null_mut());
+        if size == 0 {
+            return Err(ErrorCode::last_system());
+        }
+        result.reserve(size as usize);
+        let size = WideCharToMultiByte(code_page, 0, buffer.as_ptr(), buffer.len() as c_int, result.as_mut_ptr() as *mut i8, size, ptr::null(), ptr::null_mut());
+        result.set_len(size as usize);
+    }
+
+    Ok(result)
+}
+
+///Returns ANSI code page of the locale, if it has any.
+fn locale_code_page(lcid: DWORD) -> Option<c_uint> {
+    let mut code_page: DWORD = 0;
+    let result = unsafe {
+        GetLocaleInfoW(lcid, LOCALE_IDEFAULTANSICODEPAGE | LOCALE_RETURN_NUMBER, &mut code_page as *mut DWORD as *mut u16, (mem::size_of::<DWORD>() / mem::size_of::<u16>()) as c_int)
+    };
+
+    match result {
+        0 => None,
+        //Unicode only locale
+        _ if code_page == CP_ACP => None,
+        _ => Some(code_page),
+    }
+}
+
+///Retrieves locale identifier (`LCID`) associated with text, which is stored as `CF_LOCALE`.
+///
+///Returns `None` if `CF_LOCALE` is absent.
+///
+///# Pre-conditions:
+///
+///* [open()](fn.open.html) has been called.
+pub fn get_locale() -> Option<u32> {
+    let ptr = RawMem::from_borrowed(get_clipboard_data(formats::CF_LOCALE).ok()?);
+    let (data_ptr, _lock) = ptr.lock().ok()?;
+
+    if unsafe { GlobalSize(ptr.get()) as usize } < mem::size_of::<DWORD>() {
+        return None;
+    }
+    Some(unsafe {
+        ptr::read_unaligned(data_ptr.as_ptr() as *const DWORD)
+    })
+}
+
+#[inline(always)]
+///Returns ANSI code page of the locale stored as `CF_LOCALE`, if any.
+fn clipboard_code_page() -> Option<c_uint> {
+    get_locale().and_then(locale_code_page)
 }
 
 #[inline(always)]
@@ -159,6 +343,25 @@ pub fn seq_num() -> Option<NonZeroU32> {
     unsafe { NonZeroU32::new(GetClipboardSequenceNumber()) }
 }
 
+///Checks whether clipboard changed since `last` sequence number, storing current one in `last`.
+///
+///Intended for polling, when it is not possible to use window to listen for updates.
+///Initially `last` should be `None`, in which case any available sequence number is reported as change.
+///
+///If [seq_num](fn.seq_num.html) is not available, returns `false` and leaves `last` unchanged.
+pub fn has_changed(last: &mut Option<NonZeroU32>) -> bool {
+    match seq_num() {
+        Some(current) => match *last == Some(current) {
+            true => false,
+            false => {
+                *last = Some(current);
+                true
+            }
+        },
+        None => false,
+    }
+}
+
 #[inline]
 ///Retrieves size of clipboard data for specified format.
 ///
@@ -175,7 +378,7 @@ pub fn seq_num() -> Option<NonZeroU32> {
 ///In some cases, clipboard content might be so invalid that it crashes on `GlobalSize` (e.g.
 ///Bitmap)
 ///
-///Due to that function is marked as unsafe
+///Due to that function is marked as unsafe. Use [size_checked](fn.size_checked.html) to avoid it.
 pub unsafe fn size_unsafe(format: u32) -> Option<NonZeroUsize> {
     let clipboard_data = GetClipboardData(format);
 
@@ -185,6 +388,26 @@ pub unsafe fn size_unsafe(format: u32) -> Option<NonZeroUsize> {
     }
 }
 
+#[inline]
+///Retrieves size of clipboard data for specified format, without calling `GlobalSize` on handles.
+///
+///Bitmap handles (`CF_BITMAP` and `CF_DSPBITMAP`) are measured same as [size](fn.size.html) does.
+///Other [handle](../formats/fn.is_handle.html) formats are excluded and `None` is returned for them:
+///
+///- `CF_PALETTE`, `CF_ENHMETAFILE`, `CF_OWNERDISPLAY`, `CF_DSPENHMETAFILE`;
+///- `CF_GDIOBJFIRST..=CF_GDIOBJLAST` range.
+///
+///# Pre-conditions:
+///
+///* [open()](fn.open.html) has been called.
+pub fn size_checked(format: u32) -> Option<NonZeroUsize> {
+    match format {
+        formats::CF_BITMAP | formats::CF_DSPBITMAP => size(format),
+        format if formats::is_handle(format) => None,
+        format => unsafe { size_unsafe(format) },
+    }
+}
+
 #[inline]
 ///Retrieves size of clipboard data for specified format.
 ///
@@ -195,6 +418,13 @@ pub unsafe fn size_unsafe(format: u32) -> Option<NonZeroUsize> {
 ///# Returns:
 ///
 ///Size in bytes if format is presents on clipboard.
+///
+///For bitmap handles (`CF_BITMAP` and `CF_DSPBITMAP`) it is size of pixels, as reported by `GetObjectW`.
+///Other [handle](../formats/fn.is_handle.html) formats (`CF_PALETTE`, `CF_ENHMETAFILE`, `CF_DSPENHMETAFILE` and `CF_GDIOBJFIRST..=CF_GDIOBJLAST` range)
+///have no size, hence `None` is returned.
+///
+///Remaining formats, including `CF_METAFILEPICT` and `CF_PRIVATEFIRST..=CF_PRIVATELAST` range, are global memory,
+///which size is reported by `GlobalSize`.
 pub fn size(format: u32) -> Option<NonZeroUsize> {
     let clipboard_data = unsafe {GetClipboardData(format)};
 
@@ -202,6 +432,13 @@ pub fn size(format: u32) -> Option<NonZeroUsize> {
         return None
     }
 
+    match format {
+        formats::CF_BITMAP | formats::CF_DSPBITMAP => return bitmap_handle_size(clipboard_data),
+        //GDI objects are not global memory, hence GlobalSize cannot be used on them
+        format if formats::is_handle(format) => return None,
+        _ => (),
+    }
+
     unsafe {
         if GlobalLock(clipboard_data).is_null() {
             return None;
@@ -215,6 +452,24 @@ pub fn size(format: u32) -> Option<NonZeroUsize> {
     }
 }
 
+fn bitmap_handle_size(handle: HANDLE) -> Option<NonZeroUsize> {
+    let mut bitmap = BITMAP {
+        bmType: 0,
+        bmWidth: 0,
+        bmHeight: 0,
+        bmWidthBytes: 0,
+        bmPlanes: 0,
+        bmBitsPixel: 0,
+        bmBits: ptr::null_mut(),
+    };
+
+    if unsafe { GetObjectW(handle, mem::size_of::<BITMAP>() as _, &mut bitmap as *mut BITMAP as _) } == 0 {
+        return None;
+    }
+
+    NonZeroUsize::new(bitmap.bmWidthBytes.unsigned_abs() as usize * bitmap.bmHeight.unsigned_abs() as usize)
+}
+
 #[inline(always)]
 ///Retrieves raw pointer to clipboard data.
 ///
@@ -233,24 +488,238 @@ pub fn get_clipboard_data(format: c_uint) -> SysResult<ptr::NonNull<c_void>> {
     }
 }
 
+#[inline(always)]
+///Retrieves raw handle to clipboard data of specified `format`, allowing to pass it to other code without copying.
+///
+///Handle is owned by clipboard and must not be freed.
+///It is only valid while clipboard remains open and its content is not changed.
+///Use [ClipboardMap::from_handle](struct.ClipboardMap.html#method.from_handle) to access its memory.
+///
+///# Pre-conditions:
+///
+///* [open()](fn.open.html) has been called.
+pub fn get_handle(format: u32) -> SysResult<ptr::NonNull<c_void>> {
+    get_clipboard_data(format)
+}
+
+///Read-only view over clipboard data, mapped without copying.
+///
+///Data stays locked while instance is alive and is unlocked on drop.
+pub struct ClipboardMap<'a> {
+    data: &'a [u8],
+    _lock: Scope<*mut c_void>,
+}
+
+impl<'a> ClipboardMap<'a> {
+    ///Locks clipboard data of specified `format`.
+    ///
+    ///# Safety:
+    ///
+    ///Clipboard must remain open while instance is alive.
+    ///Use [Clipboard::map](../struct.Clipboard.html#method.map) to have it guaranteed.
+    pub unsafe fn new(format: u32) -> SysResult<Self> {
+        Self::from_handle(get_clipboard_data(format)?)
+    }
+
+    ///Locks memory of clipboard `handle`, as returned by [get_handle](fn.get_handle.html).
+    ///
+    ///# Safety:
+    ///
+    ///`handle` must be valid global memory handle.
+    ///If it is owned by clipboard, then clipboard must remain open while instance is alive and
+    ///while any pointer obtained from it is in use.
+    pub unsafe fn from_handle(handle: ptr::NonNull<c_void>) -> SysResult<Self> {
+        let mem = RawMem::from_borrowed(handle);
+        let (ptr, lock) = mem.lock()?;
+        let size = GlobalSize(mem.get()) as usize;
+
+        Ok(Self {
+            data: slice::from_raw_parts(ptr.as_ptr() as *const u8, size),
+            _lock: lock,
+        })
+    }
+
+    #[inline(always)]
+    ///Returns mapped data.
+    pub fn as_slice(&self) -> &[u8] {
+        self.data
+    }
+
+    #[inline(always)]
+    ///Returns pointer to locked memory, valid while instance is alive.
+    pub fn as_ptr(&self) -> *const u8 {
+        self.data.as_ptr()
+    }
+}
+
+impl core::ops::Deref for ClipboardMap<'_> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.data
+    }
+}
+
+impl AsRef<[u8]> for ClipboardMap<'_> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        self.data
+    }
+}
+
+#[cfg(feature = "std")]
+///Reader over clipboard data, streaming it without copying whole content at once.
+///
+///Data stays locked while instance is alive and is unlocked on drop.
+pub struct ClipboardReader<'a> {
+    map: ClipboardMap<'a>,
+    pos: usize,
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for ClipboardReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = &self.map.as_slice()[self.pos..];
+        let size = core::cmp::min(remaining.len(), buf.len());
+        buf[..size].copy_from_slice(&remaining[..size]);
+        self.pos += size;
+        Ok(size)
+    }
+}
+
+#[cfg(feature = "std")]
+#[inline(always)]
+///Locks clipboard data of specified `format`, returning reader over it.
+///
+///# Safety:
+///
+///Clipboard must remain open while reader is alive.
+///Use [Clipboard::reader](../struct.Clipboard.html#method.reader) to have it guaranteed.
+pub unsafe fn get_read<'a>(format: u32) -> SysResult<ClipboardReader<'a>> {
+    Ok(ClipboardReader {
+        map: ClipboardMap::new(format)?,
+        pos: 0,
+    })
+}
+
+///Determines whether `format` is advertised, but its data cannot be retrieved, which happens when
+///delayed rendering has not been done.
+///
+///This is diagnostic to understand why reading available format fails.
+///
+///Note that there is no way to check it without `GetClipboardData`, which requests owner to render
+///data, therefore `false` is returned if owner rendered data in response (or data was already present).
+///
+///Returns error if retrieval fails for reason unrelated to rendering (e.g. clipboard is not open).
+///
+///# Pre-conditions:
+///
+///* [open()](fn.open.html) has been called.
+pub fn is_render_pending(format: c_uint) -> SysResult<bool> {
+    const ERROR_CLIPBOARD_NOT_OPEN: i32 = 1418;
+
+    if !is_format_avail(format) {
+        return Ok(false);
+    }
+
+    unsafe {
+        SetLastError(0);
+    }
+    match get_clipboard_data(format) {
+        Ok(_) => Ok(false),
+        Err(error) if error.raw_code() == ERROR_CLIPBOARD_NOT_OPEN => Err(error),
+        Err(_) => Ok(true),
+    }
+}
+
 #[inline(always)]
 ///Determines whenever provided clipboard format is available on clipboard or not.
 pub fn is_format_avail(format: c_uint) -> bool {
     unsafe { IsClipboardFormatAvailable(format) != 0 }
 }
 
-#[inline(always)]
-///Returns the first available format in the specified list.
+#[cfg(feature = "std")]
+///Waits for `format` to become available on clipboard, until `timeout` elapses.
 ///
-///Returns `None` if no format is available or clipboard is empty
-pub fn which_format_avail(formats: &[c_uint]) -> Option<NonZeroU32> {
+///Availability is polled with exponential backoff, starting from 1ms up to 64ms between checks.
+///
+///Returns `Ok(false)` on timeout, or error if availability check fails.
+pub fn wait_for_format(format: c_uint, timeout: core::time::Duration) -> SysResult<bool> {
+    const MAX_BACKOFF: core::time::Duration = core::time::Duration::from_millis(64);
+
+    let start = std::time::Instant::now();
+    let mut backoff = core::time::Duration::from_millis(1);
+    loop {
+        unsafe {
+            SetLastError(0);
+            if IsClipboardFormatAvailable(format) != 0 {
+                return Ok(true);
+            }
+        }
+        let error = ErrorCode::last_system();
+        if error.raw_code() != 0 {
+            return Err(error);
+        }
+
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Ok(false);
+        }
+
+        std::thread::sleep(cmp::min(backoff, timeout - elapsed));
+        backoff = cmp::min(backoff * 2, MAX_BACKOFF);
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Result of [priority_format](fn.priority_format.html)
+pub enum PriorityFormat {
+    ///First format of the list, which is available.
+    Available(NonZeroU32),
+    ///Clipboard has data, but none of listed formats is available.
+    NotAvailable,
+    ///Clipboard has no data at all.
+    Empty,
+}
+
+///Returns the first available format in the specified list, in order of priority.
+///
+///Wrapper around `GetPriorityClipboardFormat`, which returns `0` for empty clipboard and `-1` if
+///none of `formats` is available.
+///
+///Empty `formats` list is treated as no format being available, unless clipboard is empty.
+pub fn priority_format(formats: &[c_uint]) -> PriorityFormat {
+    if formats.is_empty() {
+        return match count_formats() {
+            Some(0) => PriorityFormat::Empty,
+            _ => PriorityFormat::NotAvailable,
+        };
+    }
+
     let result = unsafe {
         GetPriorityClipboardFormat(formats.as_ptr(), formats.len() as _)
     };
     if result < 0 {
-        None
+        PriorityFormat::NotAvailable
     } else {
-        NonZeroU32::new(result as _)
+        match NonZeroU32::new(result as _) {
+            Some(format) => PriorityFormat::Available(format),
+            None => PriorityFormat::Empty,
+        }
+    }
+}
+
+#[inline(always)]
+///Returns the first available format in the specified list.
+///
+///Returns `None` if `formats` is empty, no format is available or clipboard is empty.
+///
+///Use [priority_format](fn.priority_format.html) to distinguish these cases.
+pub fn which_format_avail(formats: &[c_uint]) -> Option<NonZeroU32> {
+    match priority_format(formats) {
+        PriorityFormat::Available(format) => Some(format),
+        PriorityFormat::NotAvailable | PriorityFormat::Empty => None,
     }
 }
 
@@ -260,7 +729,11 @@ pub fn which_format_avail(formats: &[c_uint]) -> Option<NonZeroU32> {
 ///
 ///Returns `None` if `CountClipboardFormats` failed.
 pub fn count_formats() -> Option<usize> {
-    let result = unsafe { CountClipboardFormats() };
+    //Zero is valid result, so make sure error is not left over from previous call
+    let result = unsafe {
+        SetLastError(0);
+        CountClipboardFormats()
+    };
 
     if result == 0 {
         if ErrorCode::last_system().raw_code() != 0 {
@@ -281,18 +754,14 @@ pub fn get(format: u32, out: &mut [u8]) -> SysResult<usize> {
     if size == 0 {
         return Ok(unlikely_empty_size_result());
     }
-    let out_ptr = out.as_mut_ptr();
 
     let ptr = RawMem::from_borrowed(get_clipboard_data(format)?);
 
-    let result = unsafe {
-        let (data_ptr, _lock) = ptr.lock()?;
-        let data_size = cmp::min(GlobalSize(ptr.get()) as usize, size);
-        ptr::copy_nonoverlapping(data_ptr.as_ptr() as *const u8, out_ptr, data_size);
-        data_size
-    };
+    let data = ptr.lock_bytes()?;
+    let data_size = cmp::min(data.len(), size);
+    out[..data_size].copy_from_slice(&data[..data_size]);
 
-    Ok(result)
+    Ok(data_size)
 }
 
 ///Copies raw bytes from clipboard with specified `format`, appending to `out` buffer.
@@ -301,38 +770,61 @@ pub fn get(format: u32, out: &mut [u8]) -> SysResult<usize> {
 pub fn get_vec(format: u32, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
     let ptr = RawMem::from_borrowed(get_clipboard_data(format)?);
 
-    let result = unsafe {
-        let (data_ptr, _lock) = ptr.lock()?;
-        let data_size = GlobalSize(ptr.get()) as usize;
+    let data = ptr.lock_bytes()?;
+    out.extend_from_slice(&data);
 
-        out.reserve(data_size as usize);
-        let storage_cursor = out.len();
-        let storage_ptr = out.as_mut_ptr().add(out.len()) as *mut _;
+    Ok(data.len())
+}
 
-        ptr::copy_nonoverlapping(data_ptr.as_ptr() as *const u8, storage_ptr, data_size);
-        out.set_len(storage_cursor + data_size as usize);
+#[inline]
+///Copies raw bytes from clipboard with specified `format` into newly allocated buffer.
+///
+///Buffer is allocated with exact size of data.
+pub fn get_owned(format: u32) -> SysResult<alloc::vec::Vec<u8>> {
+    let mut out = alloc::vec::Vec::new();
+    get_vec(format, &mut out).map(|_| out)
+}
 
-        data_size
-    };
+///Copies raw bytes from clipboard with specified `format`, appending to `out` buffer in chunks.
+///
+///After each chunk `progress` is invoked with number of bytes copied so far and total number of bytes.
+///
+///Returns number of copied bytes on success, otherwise 0.
+pub fn get_vec_progress<F: FnMut(usize, usize)>(format: u32, out: &mut alloc::vec::Vec<u8>, mut progress: F) -> SysResult<usize> {
+    const CHUNK_SIZE: usize = 64 * 1024;
 
-    Ok(result)
+    let ptr = RawMem::from_borrowed(get_clipboard_data(format)?);
+
+    let data = ptr.lock_bytes()?;
+
+    out.reserve(data.len());
+    let mut copied = 0;
+    for chunk in data.chunks(CHUNK_SIZE) {
+        out.extend_from_slice(chunk);
+        copied += chunk.len();
+        progress(copied, data.len());
+    }
+
+    Ok(copied)
 }
 
 ///Retrieves HTML using format code created by `register_raw_format` or `register_format` with argument `HTML Format`
+///
+///Only fragment is appended to `out`.
+///If fragment offsets are missing or invalid, fragment is located by its comment markers.
 pub fn get_html(format: u32, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
     let ptr = RawMem::from_borrowed(get_clipboard_data(format)?);
 
-    let result = unsafe {
-        let (data_ptr, _lock) = ptr.lock()?;
-        let data_size = GlobalSize(ptr.get()) as usize;
+    let data = ptr.lock_bytes()?;
 
-        let data = match str::from_utf8(slice::from_raw_parts(data_ptr.as_ptr() as *const u8, data_size)) {
+    let result = unsafe {
+        let data = match str::from_utf8(&data) {
             Ok(data) => data,
             Err(_) => return Err(invalid_data()),
         };
 
-        let mut start_idx = 0usize;
-        let mut end_idx = data.len();
+        let mut start_idx = None;
+        let mut end_idx = None;
         for line in data.lines() {
             let mut split = line.split(html::SEP);
             let key = match split.next() {
@@ -344,39 +836,27 @@ pub fn get_html(format: u32, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize>
                 //Reached HTML
                 None => break
             };
+            //Offsets are zero-padded decimals, which parse handles as it is
             match key {
-                html::START_FRAGMENT => match value.trim_start_matches('0').parse() {
-                    Ok(value) => {
-                        start_idx = value;
-                        continue;
-                    }
-                    //Should not really happen
-                    Err(_) => break,
-                },
-                html::END_FRAGMENT => match value.trim_start_matches('0').parse() {
-                    Ok(value) => {
-                        end_idx = value;
-                        continue;
-                    }
-                    //Should not really happen
-                    Err(_) => break,
-                },
+                html::START_FRAGMENT => start_idx = value.trim().parse::<usize>().ok(),
+                html::END_FRAGMENT => end_idx = value.trim().parse::<usize>().ok(),
                 _ => continue,
             }
         }
 
-        //Make sure HTML writer didn't screw up offsets of fragment
-        let size = match end_idx.checked_sub(start_idx) {
-            Some(size) => size,
-            None => return Err(invalid_data()),
+        //Make sure HTML writer didn't screw up offsets of fragment, otherwise look for markers
+        let (start_idx, end_idx) = match (start_idx, end_idx) {
+            (Some(start), Some(end)) if start <= end && end <= data.len() && data.is_char_boundary(start) && data.is_char_boundary(end) => (start, end),
+            _ => match html::find_fragment(data) {
+                Some(fragment) => fragment,
+                None => (0, data.trim_end_matches('\0').len()),
+            }
         };
-        if size > data_size {
-            return Err(invalid_data())
-        }
+        let size = end_idx - start_idx;
 
         out.reserve(size);
         let out_cursor = out.len();
-        ptr::copy_nonoverlapping(data.as_ptr().add(start_idx), out.spare_capacity_mut().as_mut_ptr().add(out_cursor) as _, size);
+        ptr::copy_nonoverlapping(data.as_ptr().add(start_idx), out.as_mut_ptr().add(out_cursor), size);
         out.set_len(out_cursor + size);
         size
     };
@@ -384,6 +864,60 @@ pub fn get_html(format: u32, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize>
     Ok(result)
 }
 
+///Extracts code page declared by `\ansicpgN` control word of RTF document.
+fn rtf_code_page(data: &[u8]) -> Option<c_uint> {
+    const CONTROL: &[u8] = b"\\ansicpg";
+
+    let idx = data.windows(CONTROL.len()).position(|window| window == CONTROL)?;
+    let value = &data[idx + CONTROL.len()..];
+    let value_len = value.iter().take_while(|byte| byte.is_ascii_digit()).count();
+    match str::from_utf8(&value[..value_len]) {
+        Ok(value) => value.parse().ok(),
+        Err(_) => None,
+    }
+}
+
+///Retrieves Rich Text using format code created by `register_format` with argument `Rich Text Format`,
+///decoding it according to code page declared by `\ansicpgN` control word.
+///
+///If code page is not declared, text is decoded as UTF-8 when valid, otherwise using system's default code page.
+///
+///Returns number of bytes appended to `out`.
+pub fn get_rtf(format: u32, out: &mut String) -> SysResult<usize> {
+    let ptr = RawMem::from_borrowed(get_clipboard_data(format)?);
+    let data = ptr.lock_bytes()?;
+
+    //RTF is usually null terminated
+    let data = match data.iter().position(|byte| *byte == b'\0') {
+        Some(null_idx) => &data[..null_idx],
+        None => &data[..],
+    };
+
+    let code_page = if data.is_ascii() {
+        CP_UTF8
+    } else {
+        match rtf_code_page(data) {
+            Some(code_page) => code_page,
+            None => match str::from_utf8(data) {
+                Ok(_) => CP_UTF8,
+                Err(_) => CP_ACP,
+            }
+        }
+    };
+
+    if code_page == CP_UTF8 {
+        match str::from_utf8(data) {
+            Ok(data) => {
+                out.push_str(data);
+                Ok(data.len())
+            },
+            Err(_) => Err(invalid_data()),
+        }
+    } else {
+        decode_code_page(code_page, 0, data, out)
+    }
+}
+
 ///Sets HTML using format code created by `register_raw_format` or `register_format` with argument `HTML Format`
 ///
 ///Allows to customize clipboard setting behavior
@@ -485,16 +1019,19 @@ fn set_html_inner(format: u32, html: &str, empty: EmptyFn) -> SysResult<()> {
 
 fn set_inner(format: u32, data: &[u8], clear: EmptyFn) -> SysResult<()> {
     let size = data.len();
+    //Zero sized global memory is discarded, so it cannot hold data
     if size == 0 {
-        #[allow(clippy::unit_arg)]
-        return Ok(unlikely_empty_size_result());
+        return Err(ErrorCode::new_system(ERROR_INVALID_PARAMETER));
     }
 
     let mem = RawMem::new_global_mem(size)?;
+    set_mem_inner(format, mem, data, clear)
+}
 
+fn set_mem_inner(format: u32, mem: RawMem, data: &[u8], clear: EmptyFn) -> SysResult<()> {
     {
         let (ptr, _lock) = mem.lock()?;
-        unsafe { ptr::copy_nonoverlapping(data.as_ptr(), ptr.as_ptr() as _, size) };
+        unsafe { ptr::copy_nonoverlapping(data.as_ptr(), ptr.as_ptr() as _, data.len()) };
     }
 
     let _ = (clear)();
@@ -504,74 +1041,246 @@ fn set_inner(format: u32, data: &[u8], clear: EmptyFn) -> SysResult<()> {
         return Ok(());
     }
 
-    Err(ErrorCode::last_system())
-}
-/// Copies raw bytes onto clipboard with specified `format`, returning whether it was successful.
-///
-/// This function empties the clipboard before setting the data.
-pub fn set(format: u32, data: &[u8]) -> SysResult<()> {
-    set_inner(format, data, options::DoClear::EMPTY_FN)
+    Err(ErrorCode::last_system())
+}
+/// Copies raw bytes onto clipboard with specified `format`, returning whether it was successful.
+///
+/// This function empties the clipboard before setting the data.
+/// To set multiple formats use [set_without_clear](fn.set_without_clear.html) after emptying clipboard once.
+///
+/// Returns [ERROR_INVALID_PARAMETER](constant.ERROR_INVALID_PARAMETER.html) for empty `data`, leaving clipboard untouched.
+/// Unlike [set_string](fn.set_string.html), there is no representation of empty raw data as clipboard cannot hold zero sized memory.
+pub fn set(format: u32, data: &[u8]) -> SysResult<()> {
+    set_inner(format, data, options::DoClear::EMPTY_FN)
+}
+
+/// Copies raw bytes onto the clipboard with the specified `format`, returning whether it was successful.
+///
+/// This function does not empty the clipboard before setting the data.
+///
+/// Returns [ERROR_INVALID_PARAMETER](constant.ERROR_INVALID_PARAMETER.html) for empty `data`, leaving clipboard untouched.
+pub fn set_without_clear(format: u32, data: &[u8]) -> SysResult<()> {
+    set_inner(format, data, options::NoClear::EMPTY_FN)
+}
+
+/// Copies raw bytes onto clipboard with specified `format`, distinguishing failure to allocate memory.
+///
+/// Big allocation may fail due to fragmentation, even if there is enough memory in total.
+/// In this case error code is always [ERROR_NOT_ENOUGH_MEMORY](constant.ERROR_NOT_ENOUGH_MEMORY.html)
+/// and clipboard is left untouched, allowing caller to degrade (e.g. set smaller representation).
+///
+/// This function empties the clipboard before setting the data.
+///
+/// Returns [ERROR_INVALID_PARAMETER](constant.ERROR_INVALID_PARAMETER.html) for empty `data`, leaving clipboard untouched.
+pub fn set_large(format: u32, data: &[u8]) -> SysResult<()> {
+    if data.is_empty() {
+        return Err(ErrorCode::new_system(ERROR_INVALID_PARAMETER));
+    }
+
+    let mem = match RawMem::new_global_mem(data.len()) {
+        Ok(mem) => mem,
+        Err(_) => return Err(ErrorCode::new_system(ERROR_NOT_ENOUGH_MEMORY)),
+    };
+    set_mem_inner(format, mem, data, options::DoClear::EMPTY_FN)
+}
+
+///Advertises `format` for delayed rendering, without emptying clipboard.
+///
+///Clipboard must be opened with owner window using [open_for](fn.open_for.html) and emptied,
+///which makes window clipboard owner. Formats with data can be set within the same session.
+///
+///When data is requested, owner receives `WM_RENDERFORMAT` with format as `wParam` and must
+///set data without opening clipboard, e.g. using [set_without_clear](fn.set_without_clear.html).
+///Before owner is destroyed, it receives `WM_RENDERALLFORMATS` and must open clipboard, set every
+///format that is still delayed and close clipboard.
+pub fn set_delayed(format: u32) -> SysResult<()> {
+    //On success SetClipboardData returns null as there is no data
+    unsafe {
+        SetLastError(0);
+        SetClipboardData(format, ptr::null_mut());
+    }
+
+    match ErrorCode::last_system() {
+        error if error.raw_code() != 0 => Err(error),
+        _ => Ok(()),
+    }
 }
 
-/// Copies raw bytes onto the clipboard with the specified `format`, returning whether it was successful.
+#[cfg(feature = "std")]
+///Copies all bytes from `reader` onto clipboard with specified `format`, returning whether it was successful.
 ///
-/// This function does not empty the clipboard before setting the data.
-pub fn set_without_clear(format: u32, data: &[u8]) -> SysResult<()> {
-    set_inner(format, data, options::NoClear::EMPTY_FN)
+///As global memory has to be allocated with known size, `reader` content is buffered in memory first.
+///
+///This function empties the clipboard before setting the data.
+pub fn set_from_reader<R: std::io::Read>(format: u32, mut reader: R) -> SysResult<()> {
+    let mut buffer = alloc::vec::Vec::new();
+    reader.read_to_end(&mut buffer)?;
+    set(format, &buffer)
 }
 
 ///Copies raw bytes from clipboard with specified `format`, appending to `out` buffer.
 ///
 ///Returns number of copied bytes on success, otherwise 0.
 pub fn get_string(out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
-    let ptr = RawMem::from_borrowed(get_clipboard_data(formats::CF_UNICODETEXT)?);
+    get_wide_string(formats::CF_UNICODETEXT, out)
+}
+
+///Retrieves null terminated UTF-16 string of specified `format`, converting it to UTF-8 and appending to `out` buffer.
+///
+///Returns number of copied bytes on success, otherwise 0.
+///
+///String is truncated at first null character.
+pub fn get_wide_string(format: u32, out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+    get_wide_string_inner(format, out, false)
+}
+
+///Copies unicode string from clipboard, appending to `out` buffer, without truncating it at first null character.
+///
+///Whole content of clipboard memory is converted, except trailing null characters, hence
+///interior null characters are kept in output.
+///
+///Returns number of copied bytes on success, otherwise 0.
+pub fn get_string_full(out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+    get_wide_string_inner(formats::CF_UNICODETEXT, out, true)
+}
+
+fn get_wide_string_inner(format: u32, out: &mut alloc::vec::Vec<u8>, keep_nulls: bool) -> SysResult<usize> {
+    let ptr = RawMem::from_borrowed(get_clipboard_data(format)?);
 
     let result = unsafe {
         let (data_ptr, _lock) = ptr.lock()?;
         let data_size = GlobalSize(ptr.get()) as usize / mem::size_of::<u16>();
-        let storage_req_size = WideCharToMultiByte(CP_UTF8, 0, data_ptr.as_ptr() as _, data_size as _, ptr::null_mut(), 0, ptr::null(), ptr::null_mut());
+        let data = slice::from_raw_parts(data_ptr.as_ptr() as *const u16, data_size);
+        //It seems WinAPI always supposed to have at the end null char.
+        //But just to be safe let's check for it and only then remove.
+        let data = match keep_nulls {
+            true => match data.iter().rposition(|ch| *ch != 0) {
+                Some(last_idx) => &data[..=last_idx],
+                None => &data[..0],
+            },
+            false => match data.iter().position(|ch| *ch == 0) {
+                Some(null_idx) => &data[..null_idx],
+                None => data,
+            },
+        };
+
+        //Empty string cannot be converted, but it is valid content nonetheless
+        if data.is_empty() {
+            return Ok(0);
+        }
+
+        let storage_req_size = utf16_to_utf8(data, ptr::null_mut());
 
         if storage_req_size == 0 {
             return Err(ErrorCode::last_system());
         }
 
         let storage_cursor = out.len();
-        out.reserve(storage_req_size as usize);
-        let storage_ptr = out.as_mut_ptr().add(storage_cursor) as *mut _;
-        WideCharToMultiByte(CP_UTF8, 0, data_ptr.as_ptr() as _, data_size as _, storage_ptr, storage_req_size, ptr::null(), ptr::null_mut());
-        out.set_len(storage_cursor + storage_req_size as usize);
+        out.reserve(storage_req_size);
+        let storage_ptr = out.as_mut_ptr().add(storage_cursor);
+        utf16_to_utf8(data, storage_ptr);
+        out.set_len(storage_cursor + storage_req_size);
 
-        //It seems WinAPI always supposed to have at the end null char.
-        //But just to be safe let's check for it and only then remove.
-        if let Some(null_idx) = out.iter().skip(storage_cursor).position(|b| *b == b'\0') {
-            out.set_len(storage_cursor + null_idx);
+        storage_req_size
+    };
+
+    Ok(result)
+}
+
+///Retrieves ANSI text (`CF_TEXT`), decoding it and appending to `out`.
+///
+///Text is decoded using first successful option in following order:
+///
+///- UTF-8, if text is valid UTF-8;
+///- Code page of locale specified by `CF_LOCALE`, if present;
+///- System default code page, which may be lossy as last resort.
+///
+///Returns number of bytes appended.
+pub fn get_ansi_string(out: &mut String) -> SysResult<usize> {
+    let ptr = RawMem::from_borrowed(get_clipboard_data(formats::CF_TEXT)?);
+    let data = ptr.lock_bytes()?;
+
+    let data = match data.iter().position(|byte| *byte == b'\0') {
+        Some(null_idx) => &data[..null_idx],
+        None => &data[..],
+    };
+
+    if let Ok(text) = str::from_utf8(data) {
+        out.push_str(text);
+        return Ok(text.len());
+    }
+
+    if let Some(code_page) = clipboard_code_page() {
+        if let Ok(size) = decode_code_page(code_page, MB_ERR_INVALID_CHARS, data, out) {
+            return Ok(size);
         }
+    }
+
+    decode_code_page(CP_ACP, 0, data, out)
+}
 
-        out.len() - storage_cursor
+///Retrieves ANSI text (`CF_TEXT`), decoding it using code page of `CF_LOCALE` and appending to `out`.
+///
+///If `CF_LOCALE` is absent or has no ANSI code page, falls back to [get_ansi_string](fn.get_ansi_string.html).
+///
+///Returns number of bytes appended.
+pub fn get_ansi_string_by_locale(out: &mut String) -> SysResult<usize> {
+    let code_page = match clipboard_code_page() {
+        Some(code_page) => code_page,
+        None => return get_ansi_string(out),
     };
 
-    Ok(result)
+    let ptr = RawMem::from_borrowed(get_clipboard_data(formats::CF_TEXT)?);
+    let data = ptr.lock_bytes()?;
+
+    let data = match data.iter().position(|byte| *byte == b'\0') {
+        Some(null_idx) => &data[..null_idx],
+        None => &data[..],
+    };
+
+    decode_code_page(code_page, 0, data, out)
+}
+
+///Copies string onto clipboard as ANSI text (`CF_TEXT`), encoded with code page of `lcid`, together with `CF_LOCALE`.
+///
+///Use [LOCALE_USER_DEFAULT](constant.LOCALE_USER_DEFAULT.html) for current user locale.
+///If locale has no ANSI code page, system default code page is used.
+///Characters that cannot be represented are replaced with default character.
+///
+///This function empties the clipboard before setting the data.
+pub fn set_ansi_string(data: &str, lcid: u32) -> SysResult<()> {
+    let lcid = match lcid {
+        LOCALE_USER_DEFAULT => unsafe { GetUserDefaultLCID() },
+        lcid => lcid,
+    };
+    let code_page = locale_code_page(lcid).unwrap_or(CP_ACP);
+
+    let mut text = encode_code_page(code_page, data)?;
+    text.push(0);
+
+    set(formats::CF_TEXT, &text)?;
+    set_without_clear(formats::CF_LOCALE, &lcid.to_le_bytes())
 }
 
-fn set_string_inner(data: &str, clear: EmptyFn) -> SysResult<()> {
+fn set_string_inner(format: u32, data: &str, clear: EmptyFn) -> SysResult<()> {
     let size = unsafe {
-        MultiByteToWideChar(CP_UTF8, 0, data.as_ptr() as *const _, data.len() as _, ptr::null_mut(), 0)
+        utf8_to_utf16(data, ptr::null_mut())
     };
 
     //MultiByteToWideChar fails on empty input, but we can ignore it and just set buffer with null char
     if size != 0 || data.is_empty() {
-        let mem = RawMem::new_global_mem((mem::size_of::<u16>() * (size as usize + 1)) as _)?;
+        let mem = RawMem::new_global_mem(mem::size_of::<u16>() * (size + 1))?;
         {
             let (ptr, _lock) = mem.lock()?;
             let ptr = ptr.as_ptr() as *mut u16;
             unsafe {
-                MultiByteToWideChar(CP_UTF8, 0, data.as_ptr() as *const _, data.len() as _, ptr, size);
-                ptr::write(ptr.offset(size as isize), 0);
+                utf8_to_utf16(data, ptr);
+                ptr::write(ptr.add(size), 0);
             }
         }
 
         let _ = (clear)();
-        if unsafe { !SetClipboardData(formats::CF_UNICODETEXT, mem.get()).is_null() } {
+        if unsafe { !SetClipboardData(format, mem.get()).is_null() } {
             //SetClipboardData takes ownership
             mem.release();
             return Ok(());
@@ -584,8 +1293,14 @@ fn set_string_inner(data: &str, clear: EmptyFn) -> SysResult<()> {
 #[inline(always)]
 ///Copies unicode string onto clipboard, performing necessary conversions, returning true on
 ///success.
+///
+///This function empties the clipboard before setting the data.
+///To keep other formats use [set_string_with](fn.set_string_with.html) with [NoClear](../options/struct.NoClear.html).
+///
+///Empty `data` is not an error, unlike with [set](fn.set.html): it is written as single null character,
+///which is valid empty text and can be read back as empty string.
 pub fn set_string(data: &str) -> SysResult<()> {
-    set_string_inner(data, options::DoClear::EMPTY_FN)
+    set_string_inner(formats::CF_UNICODETEXT, data, options::DoClear::EMPTY_FN)
 }
 
 #[inline(always)]
@@ -595,14 +1310,83 @@ pub fn set_string(data: &str) -> SysResult<()> {
 ///Allows to customize clipboard setting behavior
 ///
 ///- `C` - Specifies clearing behavior
+///
+///Empty `data` is written as single null character, same as with [set_string](fn.set_string.html).
 pub fn set_string_with<C: Clearing>(data: &str, _is_clear: C) -> SysResult<()> {
-    set_string_inner(data, C::EMPTY_FN)
+    set_string_inner(formats::CF_UNICODETEXT, data, C::EMPTY_FN)
+}
+
+///Copies `CF_UNICODETEXT` content as UTF-16 string without conversion, appending to `out` buffer.
+///
+///String is truncated at first null character, hence terminator is not included.
+///
+///Returns number of copied UTF-16 code units on success, otherwise 0.
+pub fn get_string_wide(out: &mut alloc::vec::Vec<u16>) -> SysResult<usize> {
+    let ptr = RawMem::from_borrowed(get_clipboard_data(formats::CF_UNICODETEXT)?);
+
+    let (data_ptr, _lock) = ptr.lock()?;
+    let data_size = unsafe { GlobalSize(ptr.get()) } as usize / mem::size_of::<u16>();
+    let data = unsafe { slice::from_raw_parts(data_ptr.as_ptr() as *const u16, data_size) };
+    let data = match data.iter().position(|ch| *ch == 0) {
+        Some(null_idx) => &data[..null_idx],
+        None => data,
+    };
+
+    out.extend_from_slice(data);
+    Ok(data.len())
+}
+
+///Copies UTF-16 string onto clipboard as `CF_UNICODETEXT`, appending null terminator if it is missing.
+///
+///Fails with `ERROR_INVALID_DATA` if `data` contains unpaired surrogate, as such text may break consumers.
+///
+///Empty `data` is written as single null character, same as with [set_string](fn.set_string.html).
+///
+///This function empties the clipboard before setting the data.
+pub fn set_string_wide(data: &[u16]) -> SysResult<()> {
+    let data = match data.iter().position(|ch| *ch == 0) {
+        Some(null_idx) => &data[..null_idx],
+        None => data,
+    };
+
+    if core::char::decode_utf16(data.iter().cloned()).any(|ch| ch.is_err()) {
+        return Err(invalid_data());
+    }
+
+    let mem = RawMem::new_global_mem(mem::size_of::<u16>() * (data.len() + 1))?;
+    {
+        let (ptr, _lock) = mem.lock()?;
+        let ptr = ptr.as_ptr() as *mut u16;
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+            ptr::write(ptr.add(data.len()), 0);
+        }
+    }
+
+    let _ = empty();
+    if unsafe { !SetClipboardData(formats::CF_UNICODETEXT, mem.get()).is_null() } {
+        //SetClipboardData takes ownership
+        mem.release();
+        return Ok(());
+    }
+
+    Err(ErrorCode::last_system())
+}
+
+#[inline(always)]
+///Copies string onto clipboard with specified `format` as null terminated UTF-16 string.
+///
+///This function empties the clipboard before setting the data.
+pub fn set_wide_string(format: u32, data: &str) -> SysResult<()> {
+    set_string_inner(format, data, options::DoClear::EMPTY_FN)
 }
 
 #[cfg(feature = "std")]
 ///Retrieves file list from clipboard, appending each element to the provided storage.
 ///
-///Returns number of appended file names.
+///File names are decoded according to `DROPFILES::fWide`, either as UTF-16 or ANSI.
+///
+///Returns number of appended file names, which is 0 for empty list.
 pub fn get_file_list_path(out: &mut alloc::vec::Vec<std::path::PathBuf>) -> SysResult<usize> {
     use std::os::windows::ffi::OsStringExt;
 
@@ -642,7 +1426,9 @@ pub fn get_file_list_path(out: &mut alloc::vec::Vec<std::path::PathBuf>) -> SysR
 
 ///Retrieves file list from clipboard, appending each element to the provided storage.
 ///
-///Returns number of appended file names.
+///File names are decoded according to `DROPFILES::fWide`, either as UTF-16 or ANSI.
+///
+///Returns number of appended file names, which is 0 for empty list.
 pub fn get_file_list(out: &mut alloc::vec::Vec<alloc::string::String>) -> SysResult<usize> {
     let clipboard_data = RawMem::from_borrowed(get_clipboard_data(formats::CF_HDROP)?);
 
@@ -675,13 +1461,28 @@ pub fn get_file_list(out: &mut alloc::vec::Vec<alloc::string::String>) -> SysRes
     Ok(num_files as usize)
 }
 
-///Reads bitmap image, appending image to the `out` vector and returning number of bytes read on
-///success.
+///Retrieves drop point and `fNC` flag of `DROPFILES` structure, describing file list on clipboard.
 ///
-///Output will contain header following by RGB
-pub fn get_bitmap(out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
-    let clipboard_data = get_clipboard_data(formats::CF_BITMAP)?;
+///`fNC` indicates whether point is in non-client area of window, in which case it is in screen coordinates.
+///
+///Returns `(0, 0)` and `false` if structure is too short to contain these fields.
+pub fn get_file_list_drop_point() -> SysResult<((i32, i32), bool)> {
+    //Layout: pFiles: DWORD, pt: POINT, fNC: BOOL, fWide: BOOL
+    const PT_OFFSET: usize = mem::size_of::<DWORD>();
+    const NC_OFFSET: usize = PT_OFFSET + mem::size_of::<POINT>();
+
+    let clipboard_data = RawMem::from_borrowed(get_clipboard_data(formats::CF_HDROP)?);
+    let data = clipboard_data.lock_bytes()?;
 
+    let read_i32 = |offset: usize| i32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]]);
+    match data.len() >= NC_OFFSET + mem::size_of::<c_int>() {
+        true => Ok(((read_i32(PT_OFFSET), read_i32(PT_OFFSET + 4)), read_i32(NC_OFFSET) != 0)),
+        false => Ok(((0, 0), false)),
+    }
+}
+
+//Retrieves bitmap dimensions and prepares `BITMAPINFO` with color table to be filled by `GetDIBits`
+fn get_bitmap_info(handle: *mut c_void) -> SysResult<(u32, RawMem)> {
     //Thanks @matheuslessarodrigues
     let mut bitmap = BITMAP {
         bmType: 0,
@@ -693,7 +1494,7 @@ pub fn get_bitmap(out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
         bmBits: ptr::null_mut(),
     };
 
-    if unsafe { GetObjectW(clipboard_data.as_ptr(), mem::size_of::<BITMAP>() as _, &mut bitmap as *mut BITMAP as _) } == 0 {
+    if unsafe { GetObjectW(handle, mem::size_of::<BITMAP>() as _, &mut bitmap as *mut BITMAP as _) } == 0 {
         return Err(ErrorCode::last_system());
     }
 
@@ -732,47 +1533,125 @@ pub fn get_bitmap(out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
         header.bmiHeader.biClrUsed = 1 << clr_bits;
     }
 
-    header.bmiHeader.biSizeImage = ((((header.bmiHeader.biWidth * clr_bits + 31) & !31) / 8) * header.bmiHeader.biHeight) as _;
+    header.bmiHeader.biSizeImage = bitmap_size(&header.bmiHeader) as _;
     header.bmiHeader.biClrImportant = 0;
 
+    Ok((bitmap.bmHeight.unsigned_abs(), header_storage))
+}
+
+//Writes BMP file header, info header and color table, which precede pixels.
+fn write_bitmap_head<E, W: FnMut(&[u8]) -> Result<(), E>>(header: &BITMAPINFO, mut write: W) -> Result<(), E> {
+    write(&u16::to_le_bytes(0x4d42))?;
+    write(&u32::to_le_bytes(mem::size_of::<BITMAPFILEHEADER>() as u32 + header.bmiHeader.biSize + header.bmiHeader.biClrUsed * mem::size_of::<RGBQUAD>() as u32 + header.bmiHeader.biSizeImage))?;
+    write(&u32::to_le_bytes(0))?; //2 * u16 of 0
+    write(&u32::to_le_bytes(mem::size_of::<BITMAPFILEHEADER>() as u32 + header.bmiHeader.biSize + header.bmiHeader.biClrUsed * mem::size_of::<RGBQUAD>() as u32))?;
+
+    write(&header.bmiHeader.biSize.to_le_bytes())?;
+    write(&header.bmiHeader.biWidth.to_le_bytes())?;
+    write(&header.bmiHeader.biHeight.to_le_bytes())?;
+    write(&header.bmiHeader.biPlanes.to_le_bytes())?;
+    write(&header.bmiHeader.biBitCount.to_le_bytes())?;
+    write(&header.bmiHeader.biCompression.to_le_bytes())?;
+    write(&header.bmiHeader.biSizeImage.to_le_bytes())?;
+    write(&header.bmiHeader.biXPelsPerMeter.to_le_bytes())?;
+    write(&header.bmiHeader.biYPelsPerMeter.to_le_bytes())?;
+    write(&header.bmiHeader.biClrUsed.to_le_bytes())?;
+    write(&header.bmiHeader.biClrImportant.to_le_bytes())?;
+
+    for color in unsafe { slice::from_raw_parts(header.bmiColors.as_ptr(), header.bmiHeader.biClrUsed as _) } {
+        write(&[color.rgbBlue, color.rgbGreen, color.rgbRed, color.rgbReserved])?;
+    }
+
+    Ok(())
+}
+
+///Reads bitmap image, appending image to the `out` vector and returning number of bytes read on
+///success.
+///
+///Output will contain header following by RGB
+pub fn get_bitmap(out: &mut alloc::vec::Vec<u8>) -> SysResult<usize> {
+    let clipboard_data = get_clipboard_data(formats::CF_BITMAP)?;
+    let (height, header_storage) = get_bitmap_info(clipboard_data.as_ptr())?;
+    let header = unsafe {
+        &*(header_storage.get() as *const BITMAPINFO)
+    };
+
     let img_size = header.bmiHeader.biSizeImage as usize;
+    let colors_size = header.bmiHeader.biClrUsed as usize * mem::size_of::<RGBQUAD>();
+    let pixels_offset = mem::size_of::<BITMAPFILEHEADER>() + mem::size_of::<BITMAPINFOHEADER>() + colors_size;
     let out_before = out.len();
 
-    let dc = crate::utils::Scope(unsafe { GetDC(ptr::null_mut()) }, free_dc);
-    let mut buffer = alloc::vec![0; img_size];
+    //Pixels are read directly into output, while header is written once GetDIBits fills it
+    out.resize(out_before + pixels_offset + img_size, 0);
 
-    if unsafe { GetDIBits(dc.0, clipboard_data.as_ptr() as _, 0, bitmap.bmHeight as _, buffer.as_mut_ptr() as _, header_storage.get() as _, DIB_RGB_COLORS) } == 0 {
+    let dc = crate::utils::Scope(unsafe { GetDC(ptr::null_mut()) }, free_dc);
+    if unsafe { GetDIBits(dc.0, clipboard_data.as_ptr() as _, 0, height, out.as_mut_ptr().add(out_before + pixels_offset) as _, header_storage.get() as _, DIB_RGB_COLORS) } == 0 {
+        out.truncate(out_before);
         return Err(ErrorCode::last_system());
     }
 
-    //Write header
-    out.extend_from_slice(&u16::to_le_bytes(0x4d42));
-    out.extend_from_slice(&u32::to_le_bytes(mem::size_of::<BITMAPFILEHEADER>() as u32 + header.bmiHeader.biSize + header.bmiHeader.biClrUsed * mem::size_of::<RGBQUAD>() as u32 + header.bmiHeader.biSizeImage));
-    out.extend_from_slice(&u32::to_le_bytes(0)); //2 * u16 of 0
-    out.extend_from_slice(&u32::to_le_bytes(mem::size_of::<BITMAPFILEHEADER>() as u32 + header.bmiHeader.biSize + header.bmiHeader.biClrUsed * mem::size_of::<RGBQUAD>() as u32));
-
-    out.extend_from_slice(&header.bmiHeader.biSize.to_le_bytes());
-    out.extend_from_slice(&header.bmiHeader.biWidth.to_le_bytes());
-    out.extend_from_slice(&header.bmiHeader.biHeight.to_le_bytes());
-    out.extend_from_slice(&header.bmiHeader.biPlanes.to_le_bytes());
-    out.extend_from_slice(&header.bmiHeader.biBitCount.to_le_bytes());
-    out.extend_from_slice(&header.bmiHeader.biCompression.to_le_bytes());
-    out.extend_from_slice(&header.bmiHeader.biSizeImage.to_le_bytes());
-    out.extend_from_slice(&header.bmiHeader.biXPelsPerMeter.to_le_bytes());
-    out.extend_from_slice(&header.bmiHeader.biYPelsPerMeter.to_le_bytes());
-    out.extend_from_slice(&header.bmiHeader.biClrUsed.to_le_bytes());
-    out.extend_from_slice(&header.bmiHeader.biClrImportant.to_le_bytes());
+    let mut cursor = out_before;
+    let _ = write_bitmap_head(header, |bytes| {
+        out[cursor..cursor + bytes.len()].copy_from_slice(bytes);
+        cursor += bytes.len();
+        Ok::<_, core::convert::Infallible>(())
+    });
+    debug_assert_eq!(cursor, out_before + pixels_offset);
 
-    for color in unsafe { slice::from_raw_parts(header.bmiColors.as_ptr(), header.bmiHeader.biClrUsed as _) } {
-        out.push(color.rgbBlue);
-        out.push(color.rgbGreen);
-        out.push(color.rgbRed);
-        out.push(color.rgbReserved);
+    Ok(out.len() - out_before)
+}
+
+#[cfg(feature = "std")]
+///Reads bitmap image, writing it as BMP file into `out` and returning number of bytes written on success.
+///
+///Pixels are retrieved in bands of scan lines, limited to roughly 64KiB,
+///which are written as soon as they are read, so whole image is never held in memory.
+pub fn get_bitmap_to<W: std::io::Write>(mut out: W) -> SysResult<usize> {
+    const BAND_SIZE: usize = 64 * 1024;
+
+    let clipboard_data = get_clipboard_data(formats::CF_BITMAP)?;
+    let (height, header_storage) = get_bitmap_info(clipboard_data.as_ptr())?;
+    let header = unsafe {
+        &mut *(header_storage.get() as *mut BITMAPINFO)
+    };
+
+    let img_size = header.bmiHeader.biSizeImage as usize;
+    let colors_size = header.bmiHeader.biClrUsed as usize * mem::size_of::<RGBQUAD>();
+    let pixels_offset = mem::size_of::<BITMAPFILEHEADER>() + mem::size_of::<BITMAPINFOHEADER>() + colors_size;
+    if height == 0 {
+        write_bitmap_head(header, |bytes| out.write_all(bytes))?;
+        return Ok(pixels_offset);
     }
 
-    out.extend_from_slice(&buffer);
+    let row_size = img_size / height as usize;
+    let band_rows = cmp::max(BAND_SIZE / cmp::max(row_size, 1), 1);
+    let mut band = alloc::vec![0u8; band_rows * row_size];
 
-    Ok(out.len() - out_before)
+    let dc = crate::utils::Scope(unsafe { GetDC(ptr::null_mut()) }, free_dc);
+    let mut start = 0;
+    while start < height {
+        let rows = cmp::min(band_rows as u32, height - start);
+        //First band fills color table, hence header is written only after it
+        if unsafe { GetDIBits(dc.0, clipboard_data.as_ptr() as _, start, rows, band.as_mut_ptr() as _, header_storage.get() as _, DIB_RGB_COLORS) } == 0 {
+            return Err(ErrorCode::last_system());
+        }
+        if start == 0 {
+            //Header describes whole image, rather than band
+            header.bmiHeader.biSizeImage = img_size as _;
+            write_bitmap_head(header, |bytes| out.write_all(bytes))?;
+        }
+        out.write_all(&band[..rows as usize * row_size])?;
+        start += rows;
+    }
+
+    Ok(pixels_offset + img_size)
+}
+
+//Size of uncompressed pixels, which rows are aligned to DWORD.
+//Height is negative for top-down bitmap.
+fn bitmap_size(header: &BITMAPINFOHEADER) -> usize {
+    let row_size = ((header.biWidth.unsigned_abs() as usize * header.biBitCount as usize + 31) & !31) / 8;
+    row_size * header.biHeight.unsigned_abs() as usize
 }
 
 #[inline(always)]
@@ -783,7 +1662,9 @@ pub fn set_bitamp(data: &[u8]) -> SysResult<()> {
 
 ///Sets bitmap (header + RGB) onto clipboard, from raw bytes.
 ///
-///Returns `ERROR_INCORRECT_SIZE` if size of data is not valid
+///Returns `ERROR_INCORRECT_SIZE` if size of data is not valid, leaving clipboard untouched.
+///This includes empty `data`, as bitmap cannot be without headers, hence it is size error rather than
+///`ERROR_INVALID_PARAMETER` returned by [set](fn.set.html).
 pub fn set_bitmap(data: &[u8]) -> SysResult<()> {
     //Bitmap format cannot really overlap with much so there is no risk of having non-empty clipboard
     //Also it is backward compatible beahvior.
@@ -793,7 +1674,7 @@ pub fn set_bitmap(data: &[u8]) -> SysResult<()> {
 
 ///Sets bitmap (header + RGB) onto clipboard, from raw bytes.
 ///
-///Returns `ERROR_INCORRECT_SIZE` if size of data is not valid
+///Returns `ERROR_INCORRECT_SIZE` if size of data is not valid, including empty `data`, same as with [set_bitmap](fn.set_bitmap.html).
 ///
 ///Allows to customize clipboard setting behavior
 ///
@@ -825,7 +1706,12 @@ fn set_bitmap_inner(data: &[u8], clear: EmptyFn) -> SysResult<()> {
 
     let bitmap = &data[file_header.bfOffBits as _..];
 
-    if bitmap.len() < info_header.biSizeImage as usize {
+    //Size is optional for uncompressed bitmaps, hence it needs to be calculated
+    let image_size = match info_header.biCompression {
+        BI_RGB => cmp::max(info_header.biSizeImage as usize, bitmap_size(&info_header)),
+        _ => info_header.biSizeImage as usize,
+    };
+    if bitmap.len() < image_size {
         return Err(ErrorCode::new_system(ERROR_INCORRECT_SIZE as _));
     }
 
@@ -850,18 +1736,74 @@ fn set_bitmap_inner(data: &[u8], clear: EmptyFn) -> SysResult<()> {
 
 #[inline(always)]
 ///Set list of file paths to clipboard.
+///
+///Returns `ERROR_INCORRECT_SIZE` if `paths` is empty
 pub fn set_file_list(paths: &[impl AsRef<str>]) -> SysResult<()> {
     //See set_bitmap for reasoning of NoClear
-    set_file_list_inner(paths, options::NoClear::EMPTY_FN)
+    set_file_list_with(paths, options::NoClear)
 }
 
-#[inline(always)]
 ///Set list of file paths to clipboard.
 pub fn set_file_list_with<C: Clearing>(paths: &[impl AsRef<str>], _is_clear: C) -> SysResult<()> {
-    set_file_list_inner(paths, C::EMPTY_FN)
+    let mut file_list_size = 0;
+    for path in paths {
+        unsafe {
+            //+1 for null char
+            file_list_size += utf8_to_utf16(path.as_ref(), ptr::null_mut()) + 1
+        }
+    }
+
+    set_file_list_inner(file_list_size, |mut ptr| unsafe {
+        for path in paths {
+            let written = utf8_to_utf16(path.as_ref(), ptr);
+            ptr = ptr.add(written);
+            //Add null termination character
+            ptr.write(0);
+            ptr = ptr.add(1);
+        }
+        ptr
+    }, C::EMPTY_FN)
+}
+
+#[cfg(feature = "std")]
+#[inline(always)]
+///Set list of file paths to clipboard.
+///
+///Unlike [set_file_list](fn.set_file_list.html) accepts paths that are not valid unicode.
+///
+///Returns `ERROR_INCORRECT_SIZE` if `paths` is empty
+pub fn set_file_list_path(paths: &[impl AsRef<std::path::Path>]) -> SysResult<()> {
+    //See set_bitmap for reasoning of NoClear
+    set_file_list_path_with(paths, options::NoClear)
+}
+
+#[cfg(feature = "std")]
+///Set list of file paths to clipboard.
+pub fn set_file_list_path_with<C: Clearing>(paths: &[impl AsRef<std::path::Path>], _is_clear: C) -> SysResult<()> {
+    use std::os::windows::ffi::OsStrExt;
+
+    let mut file_list_size = 0;
+    for path in paths {
+        //+1 for null char
+        file_list_size += path.as_ref().as_os_str().encode_wide().count() + 1;
+    }
+
+    set_file_list_inner(file_list_size, |mut ptr| unsafe {
+        for path in paths {
+            for ch in path.as_ref().as_os_str().encode_wide() {
+                ptr.write(ch);
+                ptr = ptr.add(1);
+            }
+            //Add null termination character
+            ptr.write(0);
+            ptr = ptr.add(1);
+        }
+        ptr
+    }, C::EMPTY_FN)
 }
 
-fn set_file_list_inner(paths: &[impl AsRef<str>], empty: EmptyFn) -> SysResult<()> {
+//`write_paths` must write exactly `file_list_size` null-terminated characters, returning end of written list
+fn set_file_list_inner(file_list_size: usize, write_paths: impl FnOnce(*mut u16) -> *mut u16, empty: EmptyFn) -> SysResult<()> {
     #[repr(C, packed(1))]
     pub struct DROPFILES {
         pub p_files: u32,
@@ -871,17 +1813,8 @@ fn set_file_list_inner(paths: &[impl AsRef<str>], empty: EmptyFn) -> SysResult<(
     }
     const DROPFILES_SIZE: DWORD = core::mem::size_of::<DROPFILES>() as DWORD;
 
-    let mut file_list_size = 0;
-    for path in paths {
-        let path = path.as_ref();
-        unsafe {
-            //+1 for null char
-            file_list_size += MultiByteToWideChar(CP_UTF8, 0, path.as_ptr() as *const _, path.len() as _, ptr::null_mut(), 0) + 1
-        }
-    }
-
     if file_list_size == 0 {
-        return Err(ErrorCode::last_system());
+        return Err(ErrorCode::new_system(ERROR_INCORRECT_SIZE as _));
     }
 
     let dropfiles = DROPFILES {
@@ -891,7 +1824,7 @@ fn set_file_list_inner(paths: &[impl AsRef<str>], empty: EmptyFn) -> SysResult<(
         f_wide: 1,
     };
 
-    let mem_size = DROPFILES_SIZE as usize + (file_list_size as usize * 2) + 2; //+2 for final null char
+    let mem_size = DROPFILES_SIZE as usize + (file_list_size * 2) + 2; //+2 for final null char
     let mem = crate::utils::RawMem::new_global_mem(mem_size)?;
     {
         let (ptr, _lock) = mem.lock()?;
@@ -899,18 +1832,8 @@ fn set_file_list_inner(paths: &[impl AsRef<str>], empty: EmptyFn) -> SysResult<(
         unsafe {
             (ptr as *mut DROPFILES).write(dropfiles);
 
-            let mut ptr = ptr.add(DROPFILES_SIZE as usize) as *mut u16;
-            for path in paths {
-                let path = path.as_ref();
-                let written = MultiByteToWideChar(CP_UTF8, 0, path.as_ptr() as *const _, path.len() as _, ptr, file_list_size);
-                ptr = ptr.offset(written as isize);
-                //Add null termination character
-                ptr.write(0);
-                ptr = ptr.add(1);
-                file_list_size -= written - 1;
-            }
+            let ptr = write_paths(ptr.add(DROPFILES_SIZE as usize) as *mut u16);
             //Add final null termination, to indicate end of list
-            //null-terminate string
             ptr.write(0);
         }
     }
@@ -950,6 +1873,16 @@ impl EnumFormats {
         self.idx = 0;
         self
     }
+
+    /// Returns whether `format` is available, enumerating all formats from the start.
+    pub fn contains(format: u32) -> bool {
+        EnumFormats::new().any(|available| available == format)
+    }
+
+    /// Collects all available formats, enumerating from the start.
+    pub fn to_vec() -> alloc::vec::Vec<u32> {
+        EnumFormats::new().collect()
+    }
 }
 
 impl Iterator for EnumFormats {
@@ -998,6 +1931,15 @@ macro_rules! match_format_name_big {
     }
 }
 
+macro_rules! match_standard_format {
+    ( $name:expr, $( $f:ident ),* ) => {
+        match $name {
+            $( stringify!($f) => Some(formats::$f),)*
+            _ => None,
+        }
+    }
+}
+
 macro_rules! match_format_name {
     ( $name:expr => $out:ident, $( $f:ident ),* ) => {
         use core::fmt::Write;
@@ -1068,6 +2010,35 @@ pub fn format_name(format: u32, mut out: Buffer<'_>) -> Option<&'_ str> {
                        CF_UNICODETEXT);
 }
 
+///Returns identifier of standard format by its name, as returned by `format_name`.
+///
+///Returns `None` if name doesn't correspond to standard format.
+pub fn standard_format(name: &str) -> Option<u32> {
+    match_standard_format!(name,
+                           CF_BITMAP,
+                           CF_DIB,
+                           CF_DIBV5,
+                           CF_DIF,
+                           CF_DSPBITMAP,
+                           CF_DSPENHMETAFILE,
+                           CF_DSPMETAFILEPICT,
+                           CF_DSPTEXT,
+                           CF_ENHMETAFILE,
+                           CF_HDROP,
+                           CF_LOCALE,
+                           CF_METAFILEPICT,
+                           CF_OEMTEXT,
+                           CF_OWNERDISPLAY,
+                           CF_PALETTE,
+                           CF_PENDATA,
+                           CF_RIFF,
+                           CF_SYLK,
+                           CF_TEXT,
+                           CF_WAVE,
+                           CF_TIFF,
+                           CF_UNICODETEXT)
+}
+
 ///Returns format name based on it's code (allocating variant suitable for big names)
 ///
 ///# Parameters:
@@ -1167,8 +2138,49 @@ pub fn register_format(name: &str) -> Option<NonZeroU32> {
 ///Retrieves the window handle of the current owner of the clipboard.
 ///
 ///Returns `None` if clipboard is not owned.
+///
+///Clipboard doesn't need to be open.
 pub fn get_owner() -> Option<ptr::NonNull::<c_void>> {
     ptr::NonNull::new(unsafe {
         GetClipboardOwner()
     })
 }
+
+#[inline(always)]
+///Retrieves the window handle which currently holds clipboard open.
+///
+///Returns `None` if clipboard is not open or it was open without window.
+///
+///Clipboard doesn't need to be open.
+pub fn get_clipboard_holder() -> Option<ptr::NonNull::<c_void>> {
+    ptr::NonNull::new(unsafe {
+        GetOpenClipboardWindow()
+    })
+}
+
+#[cfg(feature = "std")]
+///Retrieves identifier of process which currently holds clipboard open.
+///
+///Returns `None` if there is no [holder](fn.get_clipboard_holder.html)
+pub fn get_clipboard_holder_pid() -> Option<u32> {
+    let holder = get_clipboard_holder()?;
+    let mut pid = 0;
+    match unsafe { GetWindowThreadProcessId(holder.as_ptr() as _, &mut pid) } {
+        0 => None,
+        _ => Some(pid),
+    }
+}
+
+#[cfg(feature = "std")]
+///Returns whether clipboard is currently open by calling thread.
+///
+///Thread is determined from [holder](fn.get_clipboard_holder.html) window using `GetWindowThreadProcessId`,
+///hence `false` is returned if clipboard was opened without owner window.
+pub fn is_open_by_current_thread() -> bool {
+    match get_clipboard_holder() {
+        Some(holder) => unsafe {
+            GetWindowThreadProcessId(holder.as_ptr() as _, ptr::null_mut()) == GetCurrentThreadId()
+        },
+        None => false,
+    }
+}
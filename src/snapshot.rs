@@ -0,0 +1,145 @@
+//! Clipboard snapshot.
+//!
+//! Allows to save clipboard content and restore it later, e.g. after clipboard has been used
+//! temporarily to perform paste.
+//!
+//! [ClipboardExport](struct.ClipboardExport.html) identifies formats by name instead, making it suitable to transfer content to another machine.
+
+use crate::{raw, formats, Clipboard, SysResult};
+use crate::utils::unlikely_last_error;
+
+///Copy of clipboard content.
+///
+///Only formats stored as self-contained global memory can be captured.
+///Other formats (see [is_self_contained](../formats/fn.is_self_contained.html)) as well as formats which
+///data cannot be retrieved (e.g. owner failed to render it) are skipped.
+pub struct ClipboardSnapshot {
+    formats: alloc::vec::Vec<(u32, alloc::vec::Vec<u8>)>,
+}
+
+impl ClipboardSnapshot {
+    ///Captures content of clipboard.
+    ///
+    ///# Pre-conditions:
+    ///
+    ///* [open()](../raw/fn.open.html) has been called.
+    pub fn capture() -> Self {
+        Self::capture_with(|_| true)
+    }
+
+    ///Captures content of clipboard, including only formats for which `filter` returns `true`.
+    ///
+    ///For example, use [is_ole_private](../formats/fn.is_ole_private.html) to skip OLE specific formats.
+    ///
+    ///# Pre-conditions:
+    ///
+    ///* [open()](../raw/fn.open.html) has been called.
+    pub fn capture_with<F: FnMut(u32) -> bool>(mut filter: F) -> Self {
+        let mut formats = alloc::vec::Vec::new();
+
+        for format in raw::EnumFormats::new().filter(|format| formats::is_self_contained(*format) && filter(*format)) {
+            let mut data = alloc::vec::Vec::new();
+            if raw::get_vec(format, &mut data).is_ok() {
+                formats.push((format, data));
+            }
+        }
+
+        Self {
+            formats
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of captured formats.
+    pub fn len(&self) -> usize {
+        self.formats.len()
+    }
+
+    #[inline(always)]
+    ///Returns whether no format has been captured.
+    pub fn is_empty(&self) -> bool {
+        self.formats.is_empty()
+    }
+
+    #[inline(always)]
+    ///Returns iterator over captured formats and their data, in order of clipboard enumeration.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &[u8])> + '_ {
+        self.formats.iter().map(|(format, data)| (*format, data.as_slice()))
+    }
+
+    ///Opens clipboard, empties it and sets every captured format.
+    ///
+    ///Formats are set in order they were captured, preserving their priority.
+    pub fn restore(&self) -> SysResult<()> {
+        let _clip = Clipboard::new_or_reuse(0)?;
+        raw::empty()?;
+
+        for (format, data) in self.formats.iter() {
+            raw::set_without_clear(*format, data)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+///Clipboard content with formats identified by name, as returned by [format_name](../raw/fn.format_name.html).
+///
+///Unlike format identifiers, names of registered formats are the same across machines.
+///
+///Same as [ClipboardSnapshot](struct.ClipboardSnapshot.html) it includes only formats stored as global memory.
+pub struct ClipboardExport {
+    ///Format names and their data, in order of clipboard enumeration.
+    pub formats: alloc::vec::Vec<(alloc::string::String, alloc::vec::Vec<u8>)>,
+}
+
+impl ClipboardExport {
+    ///Captures content of clipboard.
+    ///
+    ///# Pre-conditions:
+    ///
+    ///* [open()](../raw/fn.open.html) has been called.
+    pub fn capture() -> Self {
+        Self::capture_with(|_| true)
+    }
+
+    ///Captures content of clipboard, including only formats for which `filter` returns `true`.
+    ///
+    ///For example, use [is_ole_private](../formats/fn.is_ole_private.html) to skip OLE specific formats.
+    ///
+    ///# Pre-conditions:
+    ///
+    ///* [open()](../raw/fn.open.html) has been called.
+    pub fn capture_with<F: FnMut(u32) -> bool>(filter: F) -> Self {
+        let snapshot = ClipboardSnapshot::capture_with(filter);
+        let formats = snapshot.formats.into_iter().filter_map(|(format, data)| {
+            raw::format_name_big(format).map(|name| (name, data))
+        }).collect();
+
+        Self {
+            formats
+        }
+    }
+
+    ///Empties clipboard and sets every format, registering it if necessary.
+    ///
+    ///# Pre-conditions:
+    ///
+    ///* [open()](../raw/fn.open.html) has been called.
+    pub fn apply(&self) -> SysResult<()> {
+        raw::empty()?;
+
+        for (name, data) in self.formats.iter() {
+            let format = match raw::standard_format(name) {
+                Some(format) => format,
+                None => match raw::register_format(name) {
+                    Some(format) => format.get(),
+                    None => return Err(unlikely_last_error()),
+                },
+            };
+            raw::set_without_clear(format, data)?;
+        }
+
+        Ok(())
+    }
+}
@@ -8,9 +8,14 @@ extern "system" {
     pub fn GlobalSize(hMem: HGLOBAL) -> SIZE_T;
     pub fn GlobalAlloc(uflags: c_uint, dwbytes: SIZE_T) -> HGLOBAL;
     pub fn GetCurrentThread() -> HANDLE;
+    pub fn GetCurrentThreadId() -> DWORD;
     pub fn Sleep(dwMilliseconds: DWORD);
+    pub fn GetTickCount64() -> u64;
+    pub fn SetLastError(dwErrCode: DWORD);
 
     pub fn WideCharToMultiByte(page: c_uint, flags: c_ulong, wide_str: *const u16, wide_str_len: c_int, multi_str: *mut i8, multi_str_len: c_int, default_char: *const i8, used_default_char: *mut bool) -> c_int;
+    pub fn GetUserDefaultLCID() -> DWORD;
+    pub fn GetLocaleInfoW(Locale: DWORD, LCType: DWORD, lpLCData: *mut u16, cchData: c_int) -> c_int;
     pub fn MultiByteToWideChar(CodePage: c_uint, dwFlags: DWORD, lpMultiByteStr: *const u8, cbMultiByte: c_int, lpWideCharStr: *mut u16, cchWideChar: c_int) -> c_int;
 }
 
@@ -32,6 +37,43 @@ extern "system" {
     pub fn GetClipboardData(uFormat: c_uint) -> HANDLE;
     pub fn SetClipboardData(uFormat: c_uint, hMem: HANDLE) -> HANDLE;
     pub fn GetClipboardOwner() -> HWND;
+    pub fn GetOpenClipboardWindow() -> HWND;
+}
+
+#[cfg(feature = "std")]
+#[link(name = "user32", kind = "dylib")]
+extern "system" {
+    pub fn CreateWindowExW(dwExStyle: DWORD, lpClassName: *const u16, lpWindowName: *const u16, dwStyle: DWORD, x: c_int, y: c_int, nWidth: c_int, nHeight: c_int, hWndParent: HWND, hMenu: HANDLE, hInstance: HANDLE, lpParam: LPVOID) -> HWND;
+    pub fn DestroyWindow(hWnd: HWND) -> BOOL;
+    pub fn GetMessageW(lpMsg: *mut MSG, hWnd: HWND, wMsgFilterMin: c_uint, wMsgFilterMax: c_uint) -> BOOL;
+    pub fn DispatchMessageW(lpMsg: *const MSG) -> LRESULT;
+    pub fn PostMessageW(hWnd: HWND, Msg: c_uint, wParam: WPARAM, lParam: LPARAM) -> BOOL;
+    pub fn PostQuitMessage(nExitCode: c_int);
+    pub fn GetWindowThreadProcessId(hWnd: HWND, lpdwProcessId: *mut DWORD) -> DWORD;
+}
+
+#[cfg(feature = "monitor")]
+#[link(name = "user32", kind = "dylib")]
+extern "system" {
+    pub fn SetClipboardViewer(hWndNewViewer: HWND) -> HWND;
+    pub fn ChangeClipboardChain(hWndRemove: HWND, hWndNewNext: HWND) -> BOOL;
+    pub fn SendMessageW(hWnd: HWND, Msg: c_uint, wParam: WPARAM, lParam: LPARAM) -> LRESULT;
+    #[cfg(target_pointer_width = "64")]
+    pub fn GetWindowLongPtrW(hWnd: HWND, nIndex: c_int) -> isize;
+    #[cfg(target_pointer_width = "32")]
+    #[link_name = "GetWindowLongW"]
+    pub fn GetWindowLongPtrW(hWnd: HWND, nIndex: c_int) -> isize;
+}
+
+#[cfg(any(feature = "monitor", feature = "std"))]
+#[link(name = "user32", kind = "dylib")]
+extern "system" {
+    pub fn CallWindowProcW(lpPrevWndFunc: WNDPROC, hWnd: HWND, Msg: c_uint, wParam: WPARAM, lParam: LPARAM) -> LRESULT;
+    #[cfg(target_pointer_width = "64")]
+    pub fn SetWindowLongPtrW(hWnd: HWND, nIndex: c_int, dwNewLong: isize) -> isize;
+    #[cfg(target_pointer_width = "32")]
+    #[link_name = "SetWindowLongW"]
+    pub fn SetWindowLongPtrW(hWnd: HWND, nIndex: c_int, dwNewLong: isize) -> isize;
 }
 
 #[link(name = "shell32", kind = "dylib")]
@@ -40,6 +40,10 @@ pub type LPVOID = *mut c_void;
 pub type HDC = *mut c_void;
 pub type HDROP = *mut c_void;
 pub type HBITMAP = *mut c_void;
+pub type WPARAM = usize;
+pub type LPARAM = isize;
+pub type LRESULT = isize;
+pub type WNDPROC = Option<unsafe extern "system" fn(HWND, c_uint, WPARAM, LPARAM) -> LRESULT>;
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -48,6 +52,37 @@ pub struct POINT {
     pub y: c_long,
 }
 
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct RECT {
+    pub left: LONG,
+    pub top: LONG,
+    pub right: LONG,
+    pub bottom: LONG,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PAINTSTRUCT {
+    pub hdc: HDC,
+    pub fErase: BOOL,
+    pub rcPaint: RECT,
+    pub fRestore: BOOL,
+    pub fIncUpdate: BOOL,
+    pub rgbReserved: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct MSG {
+    pub hwnd: HWND,
+    pub message: c_uint,
+    pub wParam: WPARAM,
+    pub lParam: LPARAM,
+    pub time: DWORD,
+    pub pt: POINT,
+}
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct BITMAPINFOHEADER {
@@ -21,6 +21,91 @@ pub fn unlikely_last_error() -> ErrorCode {
     ErrorCode::last_system()
 }
 
+#[cold]
+#[inline(never)]
+pub fn invalid_data() -> ErrorCode {
+    ErrorCode::new_system(13)
+}
+
+#[cfg(feature = "std")]
+///Converts absolute Windows path into `file://` URL, percent encoding as necessary.
+pub fn file_url(path: &str) -> alloc::string::String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    let (mut result, path) = match path.strip_prefix("\\\\") {
+        //UNC path specifies host
+        Some(path) => (alloc::string::String::from("file://"), path),
+        None => (alloc::string::String::from("file:///"), path),
+    };
+    result.reserve(path.len());
+
+    for byte in path.bytes() {
+        match byte {
+            b'\\' => result.push('/'),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => result.push(byte as char),
+            byte => {
+                result.push('%');
+                result.push(HEX[(byte >> 4) as usize] as char);
+                result.push(HEX[(byte & 0xf) as usize] as char);
+            }
+        }
+    }
+
+    result
+}
+
+///Writes classic hex dump of `data`, with offset, hex and ASCII columns.
+pub fn hexdump(data: &[u8], out: &mut alloc::string::String) {
+    use fmt::Write;
+
+    for (idx, line) in data.chunks(16).enumerate() {
+        let _ = write!(out, "{:08x} ", idx * 16);
+        for column in 0..16 {
+            if column == 8 {
+                out.push(' ');
+            }
+            match line.get(column) {
+                Some(byte) => {
+                    let _ = write!(out, " {:02x}", byte);
+                },
+                None => out.push_str("   "),
+            }
+        }
+
+        out.push_str("  |");
+        for byte in line {
+            match byte.is_ascii_graphic() || *byte == b' ' {
+                true => out.push(*byte as char),
+                false => out.push('.'),
+            }
+        }
+        out.push_str("|\n");
+    }
+}
+
+///FNV-1a 64bit hasher.
+pub struct Fnv64(u64);
+
+impl Fnv64 {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+
+    #[inline]
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    #[inline(always)]
+    pub const fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 #[inline]
 fn noop(_: *mut c_void) {
 }
@@ -108,6 +193,32 @@ impl RawMem {
             None => Err(ErrorCode::last_system()),
         }
     }
+
+    pub fn lock_bytes(&self) -> SysResult<LockedBytes<'_>> {
+        let (ptr, lock) = self.lock()?;
+        let data = unsafe {
+            slice::from_raw_parts(ptr.as_ptr() as *const u8, sys::GlobalSize(self.get()) as usize)
+        };
+        Ok(LockedBytes {
+            data,
+            _lock: lock,
+        })
+    }
+}
+
+//Bytes of locked memory, which can only be accessed while lock is held
+pub struct LockedBytes<'a> {
+    data: &'a [u8],
+    _lock: Scope<*mut c_void>,
+}
+
+impl core::ops::Deref for LockedBytes<'_> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.data
+    }
 }
 
 pub struct Buffer<'a> {
@@ -1,3 +1,5 @@
+#![cfg(windows)]
+
 use clipboard_win::raw::{register_format, format_name, format_name_big};
 
 #[test]
@@ -77,3 +79,159 @@ fn custom_format_with_wide_chars() {
     let name = format_name(format, buf.as_mut_slice().into()).expect("to get format");
     assert_eq!(name, "一番");
 }
+
+#[test]
+fn shell_idlist_array_parse() {
+    use clipboard_win::formats::shell::Cida;
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&1u32.to_le_bytes());
+    data.extend_from_slice(&12u32.to_le_bytes());
+    data.extend_from_slice(&14u32.to_le_bytes());
+    //Desktop
+    data.extend_from_slice(&[0, 0]);
+    //Single item
+    data.extend_from_slice(&[4, 0, 0xAB, 0xCD, 0, 0]);
+
+    let cida = Cida::parse(&data).expect("parse CIDA");
+    assert_eq!(cida.len(), 1);
+    assert_eq!(cida.parent(), [0, 0]);
+    assert_eq!(cida.get(0).expect("get item"), [4, 0, 0xAB, 0xCD, 0, 0]);
+    assert!(cida.get(1).is_none());
+    assert_eq!(cida.iter().count(), 1);
+
+    //Item is not terminated
+    assert!(Cida::parse(&data[..data.len() - 2]).is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn sys_error_into_io() {
+    use clipboard_win::{ErrorCode, SysResult, IntoIo};
+
+    let result: SysResult<()> = Err(ErrorCode::new_system(5));
+    let error = result.into_io().expect_err("to fail");
+    assert_eq!(error.raw_os_error(), Some(5));
+    assert_eq!(error.kind(), std::io::ErrorKind::PermissionDenied);
+
+    let error = ErrorCode::from(std::io::Error::from_raw_os_error(2));
+    assert_eq!(clipboard_win::to_io_error(error).raw_os_error(), Some(2));
+}
+
+#[test]
+fn format_category() {
+    use clipboard_win::formats::{self, FormatCategory};
+
+    assert_eq!(formats::category(formats::CF_UNICODETEXT), FormatCategory::Text);
+    assert_eq!(formats::category(formats::CF_DIBV5), FormatCategory::Image);
+    assert_eq!(formats::category(formats::CF_HDROP), FormatCategory::File);
+    assert_eq!(formats::category(formats::CF_LOCALE), FormatCategory::Other);
+
+    let cases = [
+        ("HTML Format", FormatCategory::Text),
+        ("PNG", FormatCategory::Image),
+        ("FileNameW", FormatCategory::File),
+        ("Shell IDList Array", FormatCategory::File),
+        ("clipboard-win-category", FormatCategory::Other),
+    ];
+    for (name, expected) in cases.iter() {
+        let format = register_format(name).expect("To create format").get();
+        assert_eq!(formats::category(format), *expected, "{}", name);
+    }
+}
+
+#[test]
+fn standard_format_by_name() {
+    use clipboard_win::formats;
+    use clipboard_win::raw::standard_format;
+
+    assert_eq!(standard_format("CF_UNICODETEXT"), Some(formats::CF_UNICODETEXT));
+    assert_eq!(standard_format(&format_name_big(formats::CF_DIB).expect("name")), Some(formats::CF_DIB));
+    assert_eq!(standard_format("HTML Format"), None);
+}
+
+#[test]
+fn classify_text_kind() {
+    use clipboard_win::formats::TextKind;
+
+    assert_eq!(TextKind::classify(" HTTPS://github.com/DoumanAsh\r\n"), TextKind::Url);
+    assert_eq!(TextKind::classify("mailto:douman@gmx.se"), TextKind::Url);
+    assert_eq!(TextKind::classify("C:\\Windows\\System32"), TextKind::Path);
+    assert_eq!(TextKind::classify("\\\\server\\share"), TextKind::Path);
+    assert_eq!(TextKind::classify("fn main() {\n    println!(\"hello\");\n}"), TextKind::Code);
+    assert_eq!(TextKind::classify("Just a line"), TextKind::Plain);
+    assert_eq!(TextKind::classify("First paragraph.\n\nSecond paragraph."), TextKind::Plain);
+    assert_eq!(TextKind::classify(""), TextKind::Plain);
+}
+
+#[test]
+fn handle_formats() {
+    use clipboard_win::formats::{self, is_handle, is_self_contained};
+
+    assert!(is_handle(formats::CF_BITMAP));
+    assert!(!is_self_contained(formats::CF_BITMAP));
+    assert!(!is_handle(formats::CF_METAFILEPICT));
+    assert!(!is_self_contained(formats::CF_METAFILEPICT));
+    assert!(!is_handle(formats::CF_PRIVATEFIRST));
+    assert!(!is_self_contained(formats::CF_PRIVATEFIRST));
+    assert!(!is_handle(formats::CF_UNICODETEXT));
+    assert!(is_self_contained(formats::CF_UNICODETEXT));
+}
+
+#[test]
+fn ole_private_format() {
+    use clipboard_win::formats::{self, is_ole_private};
+
+    assert!(is_ole_private(register_format("Ole Private Data").expect("To create format").get()));
+    assert!(is_ole_private(register_format("DataObject").expect("To create format").get()));
+    assert!(!is_ole_private(register_format(formats::office::OBJECT_DESCRIPTOR).expect("To create format").get()));
+    assert!(!is_ole_private(register_format("HTML Format").expect("To create format").get()));
+    assert!(!is_ole_private(formats::CF_UNICODETEXT));
+}
+
+#[test]
+fn lazy_format_shared_across_threads() {
+    use clipboard_win::formats::LazyFormat;
+
+    static FORMAT: LazyFormat = LazyFormat::new("clipboard-win-lazy");
+
+    let expected = register_format(FORMAT.name()).expect("To create format").get();
+    let threads: Vec<_> = (0..4).map(|_| std::thread::spawn(|| FORMAT.id())).collect();
+    for thread in threads {
+        assert_eq!(thread.join().expect("join"), expected);
+    }
+    assert_eq!(FORMAT.id(), expected);
+}
+
+#[test]
+fn register_cached_format() {
+    use clipboard_win::formats::register_cached;
+
+    let expected = register_format("clipboard-win-cached").expect("To create format");
+    let threads: Vec<_> = (0..4).map(|_| std::thread::spawn(|| register_cached("clipboard-win-cached"))).collect();
+    for thread in threads {
+        assert_eq!(thread.join().expect("join"), Some(expected));
+    }
+    assert_eq!(register_cached("clipboard-win-cached"), Some(expected));
+    assert_ne!(register_cached("clipboard-win-cached-other"), Some(expected));
+}
+
+#[test]
+fn standard_format_conversion() {
+    use clipboard_win::formats::{self, StandardFormat};
+
+    assert_eq!(StandardFormat::from_u32(formats::CF_UNICODETEXT), Some(StandardFormat::UnicodeText));
+    assert_eq!(StandardFormat::from_u32(formats::CF_PRIVATEFIRST + 1), Some(StandardFormat::Private(1)));
+    assert_eq!(StandardFormat::from_u32(formats::CF_GDIOBJLAST), Some(StandardFormat::GdiObj(0xFF)));
+    assert_eq!(StandardFormat::from_u32(0xC000), None);
+
+    for format in 1..0x400 {
+        if let Some(standard) = StandardFormat::from_u32(format) {
+            assert_eq!(standard.as_u32(), format);
+            assert_eq!(u32::from(standard), format);
+        }
+    }
+
+    assert_eq!(StandardFormat::Text.name(), "CF_TEXT");
+    assert_eq!(StandardFormat::Private(1).name(), "CF_PRIVATE1");
+}
@@ -1,4 +1,6 @@
-use clipboard_win::{Clipboard, Monitor, set_clipboard_string};
+#![cfg(windows)]
+
+use clipboard_win::{Clipboard, Monitor, Unicode, set_clipboard_string};
 
 #[test]
 fn should_get_clipboard_event() {
@@ -23,7 +25,30 @@ fn should_get_clipboard_event() {
     set_clipboard_string("test").expect("Success");
     let result = monitor.recv().expect("Success");
     assert!(result);
+    drop(_clip);
+    let text = monitor.read_on_change(|clip| clip.get::<String, _>(Unicode)).expect("Read on change");
+    assert_eq!(text, "test");
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
     monitor.shutdown_channel();
     let result = monitor.recv().expect("Success");
     assert!(!result);
+
+    set_clipboard_string("test").expect("Success");
+    monitor.shutdown_channel();
+    let events = monitor.iter().collect::<Result<Vec<_>, _>>().expect("Success");
+    assert_eq!(events.len(), 1);
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn should_get_clipboard_event_async() {
+    use clipboard_win::AsyncMonitor;
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build().expect("create runtime");
+    runtime.block_on(async {
+        let mut monitor = AsyncMonitor::new().expect("create monitor");
+
+        set_clipboard_string("async").expect("Success");
+        assert!(monitor.changed().await.expect("Success"));
+    });
 }
@@ -1,6 +1,10 @@
+#![cfg(windows)]
+
 use clipboard_win::{Getter, Setter, Clipboard, is_format_avail, types};
-use clipboard_win::raw::which_format_avail;
-use clipboard_win::formats::{Html, RawData, Unicode, Bitmap, CF_TEXT, CF_UNICODETEXT, CF_BITMAP, FileList, CF_HDROP};
+use clipboard_win::raw::{which_format_avail, PriorityFormat};
+use clipboard_win::formats::office::{self, Office};
+use clipboard_win::dib::{self, ImageKind};
+use clipboard_win::formats::{Html, Rtf, Dib, DibV5, CF_DIB, RawData, Unicode, UnicodeWide, Bitmap, CF_TEXT, CF_UNICODETEXT, CF_BITMAP, FileList, CF_HDROP};
 
 fn should_set_file_list() {
     let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
@@ -18,11 +22,239 @@ fn should_set_file_list() {
     assert_eq!(set_files, paths);
 }
 
+#[cfg(feature = "std")]
+fn should_set_file_list_path() {
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    assert!(clipboard_win::raw::set_file_list_path(&[] as &[std::path::PathBuf]).is_err());
+
+    let paths = [
+        std::fs::canonicalize("tests/test-image.bmp").expect("to get abs path"),
+        std::fs::canonicalize("tests/formats.rs").expect("to get abs path"),
+    ];
+    clipboard_win::raw::set_file_list_path_with(&paths, clipboard_win::options::DoClear).expect("set paths");
+
+    let mut set_files = Vec::<std::path::PathBuf>::new();
+    assert_eq!(clipboard_win::raw::get_file_list_path(&mut set_files).expect("read"), 2);
+    assert_eq!(set_files, paths);
+}
+
+fn should_detect_change() {
+    let mut last = None;
+    assert!(clipboard_win::raw::has_changed(&mut last));
+    assert!(last.is_some());
+    assert!(!clipboard_win::raw::has_changed(&mut last));
+
+    clipboard_win::set_clipboard_string("changed").expect("Write text");
+    assert!(clipboard_win::raw::has_changed(&mut last));
+    assert!(!clipboard_win::raw::has_changed(&mut last));
+}
+
+fn should_set_get_dibv5() {
+    //2x2 image, with transparent top right pixel
+    const PIXELS: [u8; 16] = [
+        0, 0, 255, 255, 0, 255, 0, 0,
+        255, 0, 0, 255, 255, 255, 255, 128,
+    ];
+
+    assert!(dib::Image::from_bgra(2, 2, &PIXELS[..12]).is_none());
+    let image = dib::Image::from_bgra(2, 2, &PIXELS).expect("Create image");
+    assert_eq!(image.width(), 2);
+    assert_eq!(image.height(), 2);
+    assert!(image.has_alpha());
+    assert_eq!(image.alpha_mask(), Some(0xFF000000));
+    assert_eq!(image.pixels_offset(), dib::V5_HEADER_LEN);
+    //Rows are stored bottom-up
+    assert_eq!(&image.as_bytes()[dib::V5_HEADER_LEN..], &[255, 0, 0, 255, 255, 255, 255, 128, 0, 0, 255, 255, 0, 255, 0, 0]);
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    DibV5.write_clipboard(&image).expect("Write image");
+    let result = DibV5.read_image().expect("Read image");
+    assert_eq!(result.width(), 2);
+    assert_eq!(result.height(), 2);
+    assert!(result.has_alpha());
+
+    let mut plain = image.as_bytes()[..40].to_vec();
+    plain[..4].copy_from_slice(&40u32.to_le_bytes());
+    let plain = dib::Image::new(plain).expect("Create plain image");
+    assert!(!plain.has_alpha());
+    assert!(DibV5.write_clipboard(&plain).is_err());
+}
+
+fn should_enum_formats() {
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clipboard_win::set(Unicode, "enum").expect("Write text");
+
+    let mut formats = clipboard_win::EnumFormats::new();
+    let first = formats.next().expect("Have format");
+    assert!(clipboard_win::EnumFormats::contains(CF_UNICODETEXT));
+    assert!(!clipboard_win::EnumFormats::contains(CF_HDROP));
+
+    let all = clipboard_win::EnumFormats::to_vec();
+    assert_eq!(all[0], first);
+    assert!(all.contains(&CF_UNICODETEXT));
+    assert_eq!(formats.count() + 1, all.len());
+}
+
+fn should_work_with_ansi_text_format() {
+    use clipboard_win::formats::AnsiText;
+    //Russian, which uses windows-1251
+    const LCID: u32 = 0x0419;
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    RawData(CF_TEXT).write_clipboard(&"ansi\0").expect("Write raw");
+    let mut out = String::new();
+    assert_eq!(AnsiText::new().read_clipboard(&mut out).expect("Read ansi"), 4);
+    assert_eq!(out, "ansi");
+
+    AnsiText::new().write_clipboard(&"written").expect("Write ansi");
+    assert!(is_format_avail(CF_TEXT));
+    let text: String = clipboard_win::get(AnsiText::new()).expect("Read ansi");
+    assert_eq!(text, "written");
+
+    let ansi = AnsiText::with_locale(LCID);
+    assert_eq!(ansi.locale(), LCID);
+    ansi.write_clipboard(&"Привет").expect("Write ansi");
+    assert_eq!(clipboard_win::raw::get_locale(), Some(LCID));
+    let text: String = clipboard_win::get(ansi).expect("Read ansi");
+    assert_eq!(text, "Привет");
+}
+
+#[cfg(feature = "std")]
+fn should_render_delayed() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let format = clipboard_win::register_format("clipboard-win-delayed-render").expect("Register format").get();
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let render = {
+        let calls = calls.clone();
+        clipboard_win::delayed::DelayedRender::new(&[format], move |requested| {
+            assert_eq!(requested, format);
+            calls.fetch_add(1, Ordering::SeqCst);
+            Some(b"delayed".to_vec())
+        }).expect("Create delayed render")
+    };
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    assert!(is_format_avail(format));
+    assert_eq!(clipboard_win::get_owner().expect("to have owner").as_ptr(), render.window());
+
+    {
+        let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+        let data = RawData(format).read_clipboard_vec().expect("Read delayed");
+        assert!(data.starts_with(b"delayed"));
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    //Data remains after owner is gone
+    drop(render);
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    let data = RawData(format).read_clipboard_vec().expect("Read delayed");
+    assert!(data.starts_with(b"delayed"));
+}
+
+#[cfg(feature = "std")]
+fn should_render_owner_display() {
+    use clipboard_win::delayed::{DelayedRender, OwnerDisplay};
+    use clipboard_win::formats::CF_OWNERDISPLAY;
+
+    extern "system" {
+        fn SendMessageW(window: types::HWND, msg: u32, w_param: usize, l_param: isize) -> isize;
+    }
+    const WM_ASKCBFORMATNAME: u32 = 0x030C;
+
+    struct Display;
+
+    impl OwnerDisplay for Display {
+        fn format_name(&mut self) -> Option<String> {
+            Some("Custom display".to_owned())
+        }
+    }
+
+    let render = DelayedRender::with_owner_display(&[], |_| None, Display).expect("Create delayed render");
+    assert!(is_format_avail(CF_OWNERDISPLAY));
+
+    let mut name = [0u16; 7];
+    unsafe {
+        SendMessageW(render.window(), WM_ASKCBFORMATNAME, name.len(), name.as_mut_ptr() as isize);
+    }
+    assert_eq!(String::from_utf16_lossy(&name), "Custom\0");
+}
+
+fn should_set_many() {
+    let first = clipboard_win::register_format("clipboard-win-many-first").expect("Register format").get();
+    let second = clipboard_win::register_format("clipboard-win-many-second").expect("Register format").get();
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clip.set(Unicode, &"to be cleared").expect("Write text");
+
+    clip.set_many(&[(first, b"first"), (second, b"second")]).expect("Set many");
+    assert!(!is_format_avail(CF_UNICODETEXT));
+    assert!(RawData(first).read_clipboard_vec().expect("Read first").starts_with(b"first"));
+    assert!(RawData(second).read_clipboard_vec().expect("Read second").starts_with(b"second"));
+}
+
+fn should_clear() {
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clip.set(Unicode, &"clear").expect("Write text");
+    assert!(is_format_avail(CF_UNICODETEXT));
+
+    assert!(!clip.is_empty().expect("Check not empty"));
+
+    clip.clear().expect("Clear");
+    assert!(!is_format_avail(CF_UNICODETEXT));
+    assert!(clip.is_empty().expect("Check empty"));
+}
+
+fn should_get_file_list_from_dropfiles() {
+    //pFiles, pt, fNC, fWide
+    fn dropfiles(is_wide: bool) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&20u32.to_le_bytes());
+        data.extend_from_slice(&[0u8; 12]);
+        data.extend_from_slice(&(is_wide as u32).to_le_bytes());
+        data
+    }
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    let mut data = dropfiles(false);
+    data.extend_from_slice(b"C:\\first.txt\0C:\\second.txt\0\0");
+    clipboard_win::raw::set(CF_HDROP, &data).expect("Write ANSI list");
+    let mut files = Vec::<String>::new();
+    assert_eq!(FileList.read_clipboard(&mut files).expect("read ANSI list"), 2);
+    assert_eq!(files, ["C:\\first.txt", "C:\\second.txt"]);
+
+    let mut data = dropfiles(true);
+    data.extend_from_slice(&[0u8; 4]);
+    clipboard_win::raw::set(CF_HDROP, &data).expect("Write empty list");
+    files.clear();
+    assert_eq!(FileList.read_clipboard(&mut files).expect("read empty list"), 0);
+    assert!(files.is_empty());
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&20u32.to_le_bytes());
+    data.extend_from_slice(&10i32.to_le_bytes());
+    data.extend_from_slice(&(-5i32).to_le_bytes());
+    data.extend_from_slice(&1u32.to_le_bytes());
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(b"C:\\dropped.txt\0\0");
+    clipboard_win::raw::set(CF_HDROP, &data).expect("Write list with point");
+    let info: clipboard_win::formats::FileListInfo = clipboard_win::get(FileList).expect("read list info");
+    assert_eq!(info.files, ["C:\\dropped.txt"]);
+    assert_eq!(info.point, (10, -5));
+    assert!(info.is_non_client);
+}
+
 fn should_work_with_bitmap() {
     let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
 
     let test_image_bytes = std::fs::read("tests/test-image.bmp").expect("Read test image");
     Bitmap.write_clipboard(&test_image_bytes).expect("To set image");
+    assert!(clipboard_win::size(CF_BITMAP).is_some());
+    assert_eq!(clipboard_win::raw::size_checked(CF_BITMAP), clipboard_win::size(CF_BITMAP));
 
     let mut out = Vec::new();
 
@@ -30,6 +262,47 @@ fn should_work_with_bitmap() {
 
     assert_eq!(test_image_bytes.len(), out.len());
     assert!(test_image_bytes == out);
+
+    #[cfg(feature = "std")]
+    {
+        let mut written = Vec::new();
+        assert_eq!(Bitmap.read_clipboard_to(&mut written).expect("To write image"), out.len());
+        assert!(written == out);
+    }
+}
+
+fn should_work_with_top_down_bitmap() {
+    //1x2 24-bit image with rows from top to bottom: red, blue
+    let mut bmp = Vec::new();
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&(14u32 + 40 + 8).to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&(14u32 + 40).to_le_bytes());
+    bmp.extend_from_slice(&40u32.to_le_bytes());
+    bmp.extend_from_slice(&1i32.to_le_bytes());
+    bmp.extend_from_slice(&(-2i32).to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&24u16.to_le_bytes());
+    //Compression and size of image, which is optional
+    bmp.extend_from_slice(&[0u8; 8]);
+    bmp.extend_from_slice(&[0u8; 16]);
+    bmp.extend_from_slice(&[0, 0, 255, 0, 255, 0, 0, 0]);
+
+    let image = dib::Image::from_bmp(bmp.clone()).expect("Create image");
+    assert!(image.is_top_down());
+    assert_eq!(image.height(), -2);
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    //Missing pixels of second row
+    assert!(Bitmap.write_clipboard(&&bmp[..bmp.len() - 4]).is_err());
+    Bitmap.write_clipboard(&bmp).expect("Write top-down bitmap");
+
+    let mut out = Vec::new();
+    Bitmap.read_clipboard(&mut out).expect("Read bitmap");
+    let image = dib::Image::from_bmp(out).expect("Read image");
+    assert_eq!(image.width(), 1);
+    assert_eq!(image.height(), 2);
+    assert!(!image.is_top_down());
 }
 
 fn should_work_with_string() {
@@ -37,6 +310,7 @@ fn should_work_with_string() {
 
     let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
 
+    Unicode.write_clipboard("").expect("Write text");
     Unicode.write_clipboard(&text).expect("Write text");
 
     let first_format = which_format_avail(&[CF_TEXT, CF_UNICODETEXT]).unwrap();
@@ -50,6 +324,50 @@ fn should_work_with_string() {
 
     assert_eq!(Unicode.read_clipboard(&mut output).expect("Read text"), text.len());
     assert_eq!(format!("{0}{0}", text), output);
+
+    let nul_text: Vec<u8> = "ab\0cd\0".encode_utf16().flat_map(u16::to_le_bytes).collect();
+    RawData(CF_UNICODETEXT).write_clipboard(&nul_text).expect("Write text");
+
+    output.clear();
+    assert_eq!(Unicode.read_clipboard(&mut output).expect("Read text"), 2);
+    assert_eq!(output, "ab");
+
+    output.clear();
+    assert_eq!(Unicode.read_clipboard_full(&mut output).expect("Read text"), 5);
+    assert_eq!(output, "ab\0cd");
+}
+
+fn should_get_text_trimmed() {
+    let text = " \u{3000}trim me\r\n\t";
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    Unicode.write_clipboard(&text).expect("Write text");
+
+    assert_eq!(clipboard_win::get_text_trimmed(false).expect("Read text"), text);
+    assert_eq!(clipboard_win::get_text_trimmed(true).expect("Read text"), "trim me");
+}
+
+fn should_read_dib_with_embedded_png() {
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\npng data";
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&40u32.to_le_bytes()); //biSize
+    data.extend_from_slice(&1i32.to_le_bytes()); //biWidth
+    data.extend_from_slice(&1i32.to_le_bytes()); //biHeight
+    data.extend_from_slice(&1u16.to_le_bytes()); //biPlanes
+    data.extend_from_slice(&0u16.to_le_bytes()); //biBitCount
+    data.extend_from_slice(&dib::BI_PNG.to_le_bytes()); //biCompression
+    data.extend_from_slice(&(PNG.len() as u32).to_le_bytes()); //biSizeImage
+    data.extend_from_slice(&[0; 16]); //biXPelsPerMeter, biYPelsPerMeter, biClrUsed, biClrImportant
+    data.extend_from_slice(PNG);
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    RawData(CF_DIB).write_clipboard(&data).expect("Write dib");
+
+    let image = Dib.read_image().expect("Read dib");
+    assert_eq!(image.width(), 1);
+    assert_eq!(image.embedded_image(), Some((ImageKind::Png, PNG)));
+    assert!(image.write_bmp(&mut Vec::new()).is_err());
 }
 
 fn should_work_with_wide_string() {
@@ -66,13 +384,23 @@ fn should_work_with_wide_string() {
 
     assert_eq!(Unicode.read_clipboard(&mut output).expect("Read text"), text.len());
     assert_eq!(format!("{0}{0}", text), output);
+
+    let wide: Vec<u16> = text.encode_utf16().collect();
+    let mut wide_output = Vec::new();
+    assert_eq!(UnicodeWide.read_clipboard(&mut wide_output).expect("Read wide text"), wide.len());
+    assert_eq!(wide, wide_output);
+
+    UnicodeWide.write_clipboard(&wide[..2]).expect("Write wide text");
+    output.clear();
+    Unicode.read_clipboard(&mut output).expect("Read text");
+    assert_eq!(output, "メヒ");
 }
 
 fn should_work_with_bytes() {
     let text = "Again waifu!?\0";
 
     let ascii = RawData(CF_TEXT);
-    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
 
     ascii.write_clipboard(&text).expect("Write ascii");
 
@@ -91,6 +419,579 @@ fn should_work_with_bytes() {
     }
 
     assert_eq!(format!("{0}{0}", text), output);
+
+    let mut calls = Vec::new();
+    output.clear();
+    {
+        let output = unsafe { output.as_mut_vec() };
+        let size = clipboard_win::raw::get_vec_progress(CF_TEXT, output, |copied, total| calls.push((copied, total))).expect("read ascii");
+        assert_eq!(size, output.len());
+        assert_eq!(calls.last(), Some(&(size, size)));
+    }
+    assert_eq!(text, output);
+
+    clipboard_win::raw::set_large(CF_TEXT, text.as_bytes()).expect("set large");
+    assert!(clipboard_win::raw::set_large(CF_TEXT, &[]).is_err());
+
+    let map = clip.map(CF_TEXT).expect("map ascii");
+    assert_eq!(&map[..text.len()], text.as_bytes());
+
+    let handle = clipboard_win::raw::get_handle(CF_TEXT).expect("get handle");
+    let handle_map = unsafe { clipboard_win::raw::ClipboardMap::from_handle(handle) }.expect("lock handle");
+    assert_eq!(handle_map.as_slice(), &map[..]);
+    assert_eq!(handle_map.as_ptr(), map.as_ptr());
+    drop(handle_map);
+
+    assert!(clip.format_equals(CF_TEXT, text.as_bytes()).expect("compare"));
+    assert!(!clip.format_equals(CF_TEXT, b"Again waifu!!\0").expect("compare"));
+    assert!(!clip.format_equals(CF_TEXT, &[b'A'; 1024]).expect("compare"));
+
+    let (first, size) = clip.read_scoped(CF_TEXT, |data| data[0]).expect("read scoped");
+    assert_eq!(first, b'A');
+    assert_eq!(size, map.len());
+
+    #[cfg(feature = "std")]
+    {
+        use std::io::Read;
+
+        let mut streamed = Vec::new();
+        let mut reader = clip.reader(CF_TEXT).expect("get reader");
+        let mut chunk = [0u8; 3];
+        loop {
+            match reader.read(&mut chunk).expect("read chunk") {
+                0 => break,
+                size => streamed.extend_from_slice(&chunk[..size]),
+            }
+        }
+        assert_eq!(streamed[..], map[..]);
+    }
+}
+
+#[cfg(feature = "std")]
+fn should_set_file_and_url() {
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    assert!(clip.set_file_and_url(std::path::Path::new("tests/not exists.bmp")).is_err());
+    clip.set_file_and_url(std::path::Path::new("tests/test-image.bmp")).expect("Set file and url");
+
+    let path = std::env::current_dir().expect("current dir").join("tests/test-image.bmp");
+    let mut files = Vec::<String>::new();
+    FileList.read_clipboard(&mut files).expect("read files");
+    assert_eq!(files, [path.display().to_string()]);
+
+    let mut url = String::new();
+    Unicode.read_clipboard(&mut url).expect("read url");
+    assert!(url.starts_with("file:///"));
+    assert!(url.ends_with("/tests/test-image.bmp"));
+    assert!(!url.contains('\\'));
+    assert!(!url.contains(' '));
+}
+
+#[cfg(feature = "std")]
+fn should_wait_for_format() {
+    use core::time::Duration;
+
+    let format = clipboard_win::register_format("clipboard-win-wait").expect("Register format").get();
+
+    clipboard_win::set_clipboard_string("wait").expect("Write text");
+    assert!(clipboard_win::raw::wait_for_format(CF_UNICODETEXT, Duration::from_millis(0)).expect("wait"));
+    assert!(!clipboard_win::raw::wait_for_format(format, Duration::from_millis(20)).expect("wait"));
+
+    let setter = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+        clipboard_win::raw::set(format, b"wait").expect("Write data");
+    });
+    assert!(clipboard_win::raw::wait_for_format(format, Duration::from_secs(5)).expect("wait"));
+    setter.join().expect("join setter");
+}
+
+#[cfg(feature = "std")]
+fn should_set_from_reader() {
+    let data = std::fs::read("tests/test-image.bmp").expect("Read test image");
+    let file = std::fs::File::open("tests/test-image.bmp").expect("Open test image");
+
+    let format = clipboard_win::register_format("clipboard-win-reader").expect("Register format").get();
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clipboard_win::raw::set_from_reader(format, file).expect("Set from reader");
+
+    let mut out = Vec::new();
+    RawData(format).read_clipboard(&mut out).expect("Read data");
+    assert_eq!(&out[..data.len()], data);
+}
+
+fn should_read_text_info() {
+    use clipboard_win::formats::NewlineStyle;
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    let cases = [
+        ("single", false, NewlineStyle::None),
+        ("line\r\n", true, NewlineStyle::Crlf),
+        ("line\nline\n", true, NewlineStyle::Lf),
+        ("line\r\nline\nline", false, NewlineStyle::Mixed),
+    ];
+
+    for (text, had_trailing_newline, newline_style) in cases.iter() {
+        Unicode.write_clipboard(text).expect("Write text");
+
+        let mut out = String::from("prefix\n");
+        let info = Unicode.read_clipboard_info(&mut out).expect("Read text");
+        assert_eq!(&out[7..], *text);
+        assert_eq!(info.bytes, text.len());
+        assert_eq!(info.had_trailing_newline, *had_trailing_newline);
+        assert_eq!(info.newline_style, *newline_style);
+    }
+}
+
+fn should_compute_content_hash() {
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    clip.set(Unicode, &"hash me").expect("Write text");
+    let first = clip.content_hash().expect("hash");
+    clip.set(Unicode, &"hash me").expect("Write text");
+    assert_eq!(first, clip.content_hash().expect("hash"));
+
+    clip.set(Unicode, &"hash me too").expect("Write text");
+    assert_ne!(first, clip.content_hash().expect("hash"));
+}
+
+fn should_restore_snapshot() {
+    let snapshot = {
+        let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+        clip.set(Unicode, &"original").expect("Write text");
+        clip.snapshot()
+    };
+    assert!(!snapshot.is_empty());
+    assert!(snapshot.iter().any(|(format, _)| format == CF_UNICODETEXT));
+
+    clipboard_win::set_clipboard_string("temporary").expect("Write temporary");
+    snapshot.restore().expect("restore");
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    let text: String = clipboard_win::get(Unicode).expect("Read text");
+    assert_eq!(text, "original");
+}
+
+fn should_set_keep_formats() {
+    let first = clipboard_win::register_format("clipboard-win-keep-first").expect("Register format").get();
+    let second = clipboard_win::register_format("clipboard-win-keep-second").expect("Register format").get();
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clip.set(Unicode, &"to be cleared").expect("Write text");
+
+    clip.set_keep(first, b"first").expect("Set first");
+    clip.set_keep(second, b"second").expect("Set second");
+
+    assert!(!clipboard_win::is_format_avail(CF_UNICODETEXT));
+    let mut out = Vec::new();
+    RawData(first).read_clipboard(&mut out).expect("Read first");
+    assert_eq!(&out[..5], b"first");
+    let out = RawData(second).read_clipboard_vec().expect("Read second");
+    assert_eq!(&out[..6], b"second");
+}
+
+fn should_set_text_guarded() {
+    {
+        let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+        assert!(clip.set_text_guarded("guarded").expect("Set guarded"));
+    }
+
+    let text: String = clipboard_win::get_clipboard(Unicode).expect("Read text");
+    assert_eq!(text, "guarded");
+}
+
+fn should_categorize_formats() {
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clip.set(Unicode, &"categorized").expect("Write text");
+
+    let formats = clip.categorized_formats();
+    assert!(formats.text.contains(&CF_UNICODETEXT));
+    assert!(formats.image.is_empty());
+    assert!(formats.file.is_empty());
+}
+
+fn should_work_with_url() {
+    const URL: &str = "https://github.com/DoumanAsh/clipboard-win?q=テスト";
+
+    let format = clipboard_win::formats::Url::new().expect("Register url format");
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    format.write_clipboard(&URL).expect("Write url");
+
+    let mut out = String::new();
+    assert_eq!(format.read_clipboard(&mut out).expect("Read url"), URL.len());
+    assert_eq!(out, URL);
+}
+
+fn should_export_and_import() {
+    let format = clipboard_win::register_format("clipboard-win-export").expect("Register format").get();
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clip.set(Unicode, &"exported").expect("Write text");
+    clipboard_win::raw::set_without_clear(format, b"custom").expect("Write custom");
+
+    let ole = clipboard_win::register_format("Ole Private Data").expect("Register format").get();
+    clipboard_win::raw::set_without_clear(ole, b"ole").expect("Write ole");
+    let export = clipboard_win::ClipboardExport::capture_with(|format| !clipboard_win::formats::is_ole_private(format));
+    assert!(!export.formats.iter().any(|(name, _)| name == "Ole Private Data"));
+
+    let export = clip.export().expect("export");
+    assert!(export.formats.iter().any(|(name, _)| name == "CF_UNICODETEXT"));
+    assert!(export.formats.iter().any(|(name, _)| name == "clipboard-win-export"));
+
+    clip.set(Unicode, &"overwritten").expect("Write text");
+    clip.import(&export).expect("import");
+
+    let text: String = clipboard_win::get(Unicode).expect("Read text");
+    assert_eq!(text, "exported");
+    let mut out = Vec::new();
+    RawData(format).read_clipboard(&mut out).expect("Read custom");
+    assert_eq!(&out[..6], b"custom");
+}
+
+fn should_pick_primary_format() {
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    clip.empty().expect("empty");
+    assert_eq!(clip.primary_format().expect("primary"), None);
+
+    clip.set(Unicode, &"text").expect("Write text");
+    assert_eq!(clip.primary_format().expect("primary"), Some(CF_UNICODETEXT));
+
+    let html = Html::new().expect("Create html format");
+    clipboard_win::raw::set_html_with(html.code(), "<b>html</b>", clipboard_win::options::NoClear).expect("Write html");
+    assert_eq!(clip.primary_format().expect("primary"), Some(html.code()));
+}
+
+fn should_reject_unpaired_surrogate() {
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    let text: Vec<u16> = "wide 🙂".encode_utf16().collect();
+    clipboard_win::raw::set_string_wide(&text).expect("Write wide");
+    let out: String = clipboard_win::get(Unicode).expect("Read text");
+    assert_eq!(out, "wide 🙂");
+
+    let error = clipboard_win::raw::set_string_wide(&[0x61, 0xD800, 0x62]).expect_err("Lone surrogate");
+    assert_eq!(error.raw_code(), 13);
+    let error = clipboard_win::raw::set_string_wide(&text[..text.len() - 1]).expect_err("Truncated pair");
+    assert_eq!(error.raw_code(), 13);
+
+    let out: String = clipboard_win::get(Unicode).expect("Read text");
+    assert_eq!(out, "wide 🙂");
+}
+
+fn should_upgrade_text_to_unicode() {
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    clip.set(Unicode, &"unicode").expect("Write unicode");
+    assert!(!clip.upgrade_text_to_unicode().expect("upgrade"));
+
+    RawData(CF_TEXT).write_clipboard(&"utf-8 テキスト\0").expect("Write utf-8");
+    assert!(clip.upgrade_text_to_unicode().expect("upgrade"));
+    assert!(!clip.upgrade_text_to_unicode().expect("upgrade"));
+
+    let text: String = clipboard_win::get(Unicode).expect("Read text");
+    assert_eq!(text, "utf-8 テキスト");
+    let mut out = Vec::new();
+    RawData(CF_TEXT).read_clipboard(&mut out).expect("Read ascii");
+    assert!(out.starts_with("utf-8 テキスト".as_bytes()));
+}
+
+#[cfg(feature = "monitor")]
+fn should_set_delayed_with_immediate() {
+    use windows_win::{raw, Window};
+
+    let window = Window::from_builder(raw::window::Builder::new().class_name("STATIC").parent_message()).expect("Create window");
+    let format = clipboard_win::register_format("clipboard-win-delayed").expect("Register format").get();
+
+    let clip = Clipboard::builder().owner(window.inner() as _).attempts(10).open().expect("Open clipboard");
+    #[cfg(feature = "std")]
+    assert!(clipboard_win::raw::is_open_by_current_thread());
+    clip.set(Unicode, &"immediate").expect("Write text");
+    clip.set_delayed(format).expect("Set delayed");
+
+    assert!(clipboard_win::is_format_avail(format));
+    let text: String = clipboard_win::get(Unicode).expect("Read text");
+    assert_eq!(text, "immediate");
+
+    //Window doesn't handle WM_RENDERFORMAT
+    assert!(clipboard_win::raw::is_render_pending(format).expect("check render"));
+    assert!(!clipboard_win::raw::is_render_pending(CF_UNICODETEXT).expect("check render"));
+}
+
+fn should_hexdump_format() {
+    let format = clipboard_win::register_format("clipboard-win-hexdump").expect("Register format").get();
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clip.set(RawData(format), b"Hello, hexdump!\x00\x01\xff").expect("Write data");
+
+    let dump = clip.hexdump_format(format, 18).expect("hexdump");
+    let mut lines = dump.lines();
+    assert_eq!(lines.next(), Some("00000000  48 65 6c 6c 6f 2c 20 68  65 78 64 75 6d 70 21 00  |Hello, hexdump!.|"));
+    assert_eq!(lines.next(), Some("00000010  01 ff                                             |..|"));
+    assert!(lines.next().expect("truncation").starts_with("... truncated: 18 of"));
+    assert_eq!(lines.next(), None);
+}
+
+fn should_handle_empty_input() {
+    let format = clipboard_win::register_format("clipboard-win-empty").expect("Register format").get();
+    let empty: &[u8] = &[];
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    clip.set(Unicode, &"kept").expect("Write text");
+    let error = clipboard_win::raw::set_without_clear(format, empty).expect_err("Set empty without clear");
+    assert_eq!(error.raw_code(), clipboard_win::raw::ERROR_INVALID_PARAMETER);
+    let error = clipboard_win::raw::set(format, empty).expect_err("Set empty");
+    assert_eq!(error.raw_code(), clipboard_win::raw::ERROR_INVALID_PARAMETER);
+    let error = clipboard_win::raw::set_large(format, empty).expect_err("Set large empty");
+    assert_eq!(error.raw_code(), clipboard_win::raw::ERROR_INVALID_PARAMETER);
+    assert!(clipboard_win::is_format_avail(CF_UNICODETEXT));
+    assert!(!clipboard_win::is_format_avail(format));
+
+    clipboard_win::raw::set_string("").expect("Set empty string");
+    assert!(clipboard_win::is_format_avail(CF_UNICODETEXT));
+    assert!(clipboard_win::raw::size(CF_UNICODETEXT).is_some());
+    assert_eq!(clipboard_win::raw::size_checked(CF_UNICODETEXT), clipboard_win::raw::size(CF_UNICODETEXT));
+    clipboard_win::raw::set_string_wide(&[]).expect("Set empty wide string");
+    let text: String = clipboard_win::get(Unicode).expect("Read text");
+    assert!(text.is_empty());
+
+    let error = clipboard_win::raw::set_file_list(&[] as &[&str]).expect_err("Set empty file list");
+    assert_eq!(error.raw_code(), 1462);
+}
+
+fn should_reject_empty_bitmap() {
+    let empty: &[u8] = &[];
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    clip.set(Unicode, &"kept").expect("Write text");
+    let error = clipboard_win::raw::set_bitmap(empty).expect_err("Set empty bitmap");
+    assert_eq!(error.raw_code(), 1462);
+    let error = clipboard_win::raw::set_bitmap_with(empty, clipboard_win::options::DoClear).expect_err("Set empty bitmap with clear");
+    assert_eq!(error.raw_code(), 1462);
+    let error = Bitmap.write_clipboard(&empty).expect_err("Write empty bitmap");
+    assert_eq!(error.raw_code(), 1462);
+    assert!(!clipboard_win::is_format_avail(CF_BITMAP));
+
+    let text: String = clipboard_win::get(Unicode).expect("Read text");
+    assert_eq!(text, "kept");
+}
+
+fn should_put_and_take_clipboard_format() {
+    use clipboard_win::{ClipboardFormat, SysResult};
+
+    #[derive(Debug, PartialEq)]
+    struct Point(u32, u32);
+
+    impl ClipboardFormat for Point {
+        fn format_id() -> u32 {
+            clipboard_win::register_format("clipboard-win-point").expect("Register format").get()
+        }
+
+        fn to_bytes(&self) -> Vec<u8> {
+            self.0.to_le_bytes().iter().chain(self.1.to_le_bytes().iter()).cloned().collect()
+        }
+
+        fn from_bytes(bytes: &[u8]) -> SysResult<Self> {
+            Ok(Point(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]), u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]])))
+        }
+    }
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    clip.put(&Point(1, 2)).expect("put point");
+    assert_eq!(clip.take::<Point>().expect("take point"), Point(1, 2));
+
+    clip.put(&"テキスト".to_owned()).expect("put text");
+    assert_eq!(clip.take::<String>().expect("take text"), "テキスト");
+    let text: String = clipboard_win::get(Unicode).expect("Read text");
+    assert_eq!(text, "テキスト");
+
+    clip.put(&vec![1u8, 2, 3]).expect("put bytes");
+    assert!(clip.take::<Vec<u8>>().expect("take bytes").starts_with(&[1, 2, 3]));
+}
+
+fn should_work_with_ansi_text() {
+    //Russian, which uses windows-1251
+    const LCID: u32 = 0x0419;
+    const TEXT: &str = "Привет";
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clip.set_ansi_text(TEXT, LCID).expect("Write ansi");
+    assert_eq!(clipboard_win::raw::get_locale(), Some(LCID));
+
+    let mut out = Vec::new();
+    RawData(CF_TEXT).read_clipboard(&mut out).expect("Read ansi");
+    assert_eq!(&out[..7], &[0xcf, 0xf0, 0xe8, 0xe2, 0xe5, 0xf2, 0]);
+
+    assert_eq!(clip.get_ansi_text().expect("Read ansi"), TEXT);
+    assert_eq!(clipboard_win::get_text_best_effort().expect("Read unicode"), TEXT);
+
+    clip.set_ansi_text("ascii", clipboard_win::raw::LOCALE_USER_DEFAULT).expect("Write ansi");
+    assert_eq!(clip.get_ansi_text().expect("Read ansi"), "ascii");
+}
+
+fn should_get_text_kind() {
+    use clipboard_win::formats::TextKind;
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clip.set(Unicode, &"https://docs.rs/clipboard-win").expect("Write text");
+    assert_eq!(clip.text_kind().expect("text kind"), TextKind::Url);
+}
+
+fn should_set_text_crlf() {
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    let cases = [
+        ("single", "single"),
+        ("\nfirst\nsecond\r\nthird\n", "\r\nfirst\r\nsecond\r\nthird\r\n"),
+        ("already\r\ncrlf", "already\r\ncrlf"),
+    ];
+
+    for (text, expected) in cases.iter() {
+        clipboard_win::set_text_crlf(text).expect("Write text");
+        let out: String = clipboard_win::get(Unicode).expect("Read text");
+        assert_eq!(out, *expected);
+    }
+}
+
+fn should_read_image() {
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x02\0\0\0\x03png data";
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    clip.empty().expect("empty");
+    assert!(clip.read_image().expect("read image").is_none());
+
+    let bmp = std::fs::read("tests/test-image.bmp").expect("Read test image");
+    Bitmap.write_clipboard(&bmp).expect("Write bitmap");
+    let image = clip.read_image().expect("read image").expect("to have image");
+    assert_eq!(image.width(), i32::from_le_bytes([bmp[18], bmp[19], bmp[20], bmp[21]]));
+
+    let png = clipboard_win::register_format("PNG").expect("Register format").get();
+    clipboard_win::raw::set(png, PNG).expect("Write png");
+    let image = clip.read_image().expect("read image").expect("to have image");
+    assert_eq!(image.width(), 2);
+    assert_eq!(image.height(), 3);
+    assert_eq!(image.embedded_image(), Some((ImageKind::Png, PNG)));
+}
+
+fn should_work_with_gif() {
+    //1x1 transparent GIF89a
+    const GIF: &[u8] = b"GIF89a\x01\0\x01\0\x80\0\0\0\0\0\xff\xff\xff!\xf9\x04\x01\0\0\0\0,\0\0\0\0\x01\0\x01\0\0\x02\x02D\x01\0;";
+
+    let gif = clipboard_win::formats::Gif::new().expect("Register gif format");
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    gif.write_clipboard(&GIF).expect("Write gif");
+    assert!(clipboard_win::Format::is_format_avail(&gif));
+
+    let mut out = Vec::new();
+    assert_eq!(gif.read_clipboard(&mut out).expect("Read gif"), GIF.len());
+    assert_eq!(out, GIF);
+}
+
+fn should_work_with_png() {
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x01\0\0\0\x01png data";
+
+    let png = clipboard_win::formats::Png::new().expect("Register png format");
+    assert_eq!(png.code(), clipboard_win::register_format("PNG").expect("Register png").get());
+    assert_eq!(png.code(), clipboard_win::formats::Png::new().expect("Register png format").code());
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    png.write_clipboard(&PNG).expect("Write png");
+    assert!(clipboard_win::Format::is_format_avail(&png));
+
+    let mut out = Vec::new();
+    assert_eq!(png.read_clipboard(&mut out).expect("Read png"), PNG.len());
+    assert_eq!(out, PNG);
+}
+
+fn should_set_text_and_bytes() {
+    let format = clipboard_win::register_format("clipboard-win-bytes").expect("Register format").get();
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clip.set_bytes(format, b"bytes").expect("Set bytes");
+    let data = RawData(format).read_clipboard_vec().expect("Read bytes");
+    assert!(data.starts_with(b"bytes"));
+
+    clip.set_text("text").expect("Set text");
+    assert!(!is_format_avail(format));
+    let text: String = clip.get(Unicode).expect("Read text");
+    assert_eq!(text, "text");
+    assert_eq!(clip.get_text().expect("Get text"), "text");
+    assert_eq!(clip.get_text().expect("Get text"), "text");
+}
+
+fn should_snapshot_formats() {
+    let format = clipboard_win::register_format("clipboard-win-snapshot").expect("Register format").get();
+    {
+        let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+        clip.set(Unicode, &"snapshot").expect("Write text");
+        clipboard_win::raw::set_without_clear(format, b"custom").expect("Write custom");
+    }
+
+    let formats = clipboard_win::formats::snapshot().expect("Snapshot formats");
+    assert!(formats.contains(&CF_UNICODETEXT));
+    assert!(formats.contains(&format));
+
+    let named = clipboard_win::formats::snapshot_named().expect("Snapshot named formats");
+    assert!(named.iter().any(|(id, name)| *id == format && name == "clipboard-win-snapshot"));
+    assert!(named.iter().any(|(id, name)| *id == CF_UNICODETEXT && name == "CF_UNICODETEXT"));
+}
+
+fn should_read_spreadsheet_text() {
+    use clipboard_win::formats::{Dif, Sylk, CF_DIF, CF_SYLK};
+
+    const DIF: &str = "TABLE\r\n0,1\r\n\"EXCEL\"\r\n";
+    const SYLK: &str = "ID;PWXL;N;E\r\nC;Y1;X1;K1\r\nE\r\n";
+
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    clip.empty().expect("Empty");
+    clipboard_win::raw::set_without_clear(CF_DIF, format!("{}\0", DIF).as_bytes()).expect("Write dif");
+    clipboard_win::raw::set_without_clear(CF_SYLK, format!("{}\0", SYLK).as_bytes()).expect("Write sylk");
+
+    let mut out = String::new();
+    assert_eq!(Dif.read_clipboard(&mut out).expect("Read dif"), DIF.len());
+    assert_eq!(out, DIF);
+
+    out.clear();
+    assert_eq!(Sylk.read_clipboard(&mut out).expect("Read sylk"), SYLK.len());
+    assert_eq!(out, SYLK);
+
+    clipboard_win::raw::set(CF_DIF, "ÄÖ".as_bytes()).expect("Write dif");
+    out.clear();
+    assert!(Dif.read_clipboard(&mut out).is_err());
+    assert!(out.is_empty());
+}
+
+fn should_work_with_wave() {
+    use clipboard_win::formats::{Wave, CF_WAVE};
+
+    const WAV: &[u8] = b"RIFF\x24\0\0\0WAVEfmt \x10\0\0\0";
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    Wave.write_clipboard(&WAV).expect("Write wave");
+    assert!(is_format_avail(CF_WAVE));
+
+    let mut out = Vec::new();
+    Wave.read_clipboard(&mut out).expect("Read wave");
+    assert!(out.starts_with(WAV));
+}
+
+fn should_get_text_best_effort() {
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+
+    RawData(CF_TEXT).write_clipboard(&"ascii\0").expect("Write ascii");
+    assert_eq!(clipboard_win::get_text_best_effort().expect("Read text"), "ascii");
+
+    RawData(CF_TEXT).write_clipboard(&"utf-8 テキスト\0").expect("Write utf-8");
+    assert_eq!(clipboard_win::get_text_best_effort().expect("Read text"), "utf-8 テキスト");
+
+    Unicode.write_clipboard(&"unicode").expect("Write unicode");
+    assert_eq!(clipboard_win::get_text_best_effort().expect("Read text"), "unicode");
 }
 
 fn should_work_with_set_empty_string() {
@@ -104,6 +1005,96 @@ fn should_work_with_set_empty_string() {
 
     assert_eq!(Unicode.read_clipboard(&mut output).expect("Read text"), text.len());
     assert_eq!(text, output);
+
+    //Lone null character
+    RawData(CF_UNICODETEXT).write_clipboard(&[0u8, 0]).expect("Write null");
+    let mut output = b"prefix".to_vec();
+    assert_eq!(clipboard_win::raw::get_string(&mut output).expect("Read text"), 0);
+    assert_eq!(output, b"prefix");
+}
+
+fn should_open_with_timeout() {
+    use core::time::Duration;
+
+    let clip = Clipboard::new_with_timeout(Duration::from_secs(1)).expect("Open clipboard");
+
+    let waiter = std::thread::spawn(|| {
+        let start = std::time::Instant::now();
+        let result = Clipboard::new_with_timeout(Duration::from_millis(50));
+        (result.is_err(), start.elapsed())
+    });
+    let (is_err, elapsed) = waiter.join().expect("Join waiter");
+    assert!(is_err);
+    assert!(elapsed >= Duration::from_millis(40));
+
+    //Nested open is not busy clipboard, hence it fails immediately
+    let start = std::time::Instant::now();
+    let error = Clipboard::new_wait(Duration::from_secs(10)).err().expect("Nested open to fail");
+    assert_eq!(error.raw_code(), clipboard_win::raw::ERROR_ALREADY_INITIALIZED);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    let start = std::time::Instant::now();
+    let error = Clipboard::new_with_timeout(Duration::from_secs(10)).err().expect("Nested open to fail");
+    assert_eq!(error.raw_code(), clipboard_win::raw::ERROR_ALREADY_INITIALIZED);
+    let error = Clipboard::builder().attempts(100).delay(Duration::from_millis(100)).open().err().expect("Nested open to fail");
+    assert_eq!(error.raw_code(), clipboard_win::raw::ERROR_ALREADY_INITIALIZED);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    //Other thread of the same process is busy clipboard, hence it waits for release
+    let error = std::thread::spawn(|| Clipboard::new().err().map(|error| error.raw_code())).join().expect("Join thread");
+    assert_eq!(error, Some(clipboard_win::raw::ERROR_ACCESS_DENIED));
+    let waiter = std::thread::spawn(|| Clipboard::new_wait(Duration::from_secs(5)).map(drop));
+    std::thread::sleep(Duration::from_millis(50));
+    drop(clip);
+    waiter.join().expect("Join waiter").expect("Wait for clipboard");
+}
+
+fn should_reject_nested_open() {
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    let error = Clipboard::new().err().expect("Nested open to fail");
+    assert_eq!(error.raw_code(), clipboard_win::raw::ERROR_ALREADY_INITIALIZED);
+
+    clipboard_win::set_clipboard_string("nested").expect("Set within open clipboard");
+    let text: String = clipboard_win::get_clipboard(Unicode).expect("Get within open clipboard");
+    assert_eq!(text, "nested");
+    assert!(Clipboard::new().is_err());
+    drop(clip);
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+}
+
+fn should_open_with_builder() {
+    let _clip = Clipboard::builder().attempts(10).delay(core::time::Duration::from_millis(5)).open().expect("Open clipboard");
+    Unicode.write_clipboard(&"text").expect("Write text");
+    assert!(is_format_avail(CF_UNICODETEXT));
+}
+
+fn should_work_within_open_clipboard() {
+    let text = "within open clipboard";
+
+    let result: String = clipboard_win::with_open_clipboard(10, |clip| {
+        clip.empty()?;
+        clip.set(Unicode, &text)?;
+        clip.get(Unicode)
+    }).expect("Run with clipboard");
+    assert_eq!(result, text);
+
+    let result = clipboard_win::with_clipboard_ret(|| {
+        let mut result = String::new();
+        Unicode.read_clipboard(&mut result)?;
+        Ok(result)
+    }).expect("Run with clipboard");
+    assert_eq!(result, text);
+}
+
+fn should_empty_open_clipboard() {
+    let clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    Unicode.write_clipboard(&"text").expect("Write text");
+    assert!(is_format_avail(CF_UNICODETEXT));
+
+    clip.empty().expect("Empty clipboard");
+    assert!(!is_format_avail(CF_UNICODETEXT));
+    assert_eq!(clipboard_win::priority_format(&[CF_UNICODETEXT]), PriorityFormat::Empty);
 }
 
 extern "system" {
@@ -115,6 +1106,9 @@ fn should_set_owner() {
         assert!(clipboard_win::get_owner().is_none());
         let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
         assert!(clipboard_win::get_owner().is_none());
+        assert!(clipboard_win::raw::get_clipboard_holder().is_none());
+        #[cfg(feature = "std")]
+        assert!(!clipboard_win::raw::is_open_by_current_thread());
     }
 
     let console = unsafe { GetConsoleWindow() };
@@ -122,6 +1116,9 @@ fn should_set_owner() {
         let _clip = Clipboard::new_attempts_for(console, 10).expect("Open clipboard");
         let _ = clipboard_win::empty(); //empty is necessary to finalize association
         assert_eq!(clipboard_win::get_owner().expect("to have owner").as_ptr() as usize, console as usize);
+        assert_eq!(clipboard_win::raw::get_clipboard_holder().expect("to have holder").as_ptr() as usize, console as usize);
+        #[cfg(feature = "std")]
+        assert!(clipboard_win::raw::get_clipboard_holder_pid().is_some());
     }
 }
 
@@ -133,11 +1130,14 @@ fn should_set_get_html() {
 
     assert!(!is_format_avail(html1.code()));
     assert!(which_format_avail(&[html1.code()]).is_none());
+    assert!(which_format_avail(&[]).is_none());
     let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
     html1.write_clipboard(&HTML).expect("write clipboard");
 
     assert!(is_format_avail(html1.code()));
     assert_eq!(which_format_avail(&[html1.code(), CF_TEXT]).unwrap().get(), html1.code());
+    assert_eq!(clipboard_win::priority_format(&[CF_BITMAP, html1.code()]), PriorityFormat::Available(core::num::NonZeroU32::new(html1.code()).unwrap()));
+    assert_eq!(clipboard_win::priority_format(&[CF_BITMAP]), PriorityFormat::NotAvailable);
     //This works on my PC, but not in CI, wtf MS
     //assert_eq!(which_format_avail(&[CF_TEXT, html1.code()]).unwrap().get(), html1.code());
 
@@ -152,6 +1152,59 @@ fn should_set_get_html() {
     out.clear();
     html1.read_clipboard(&mut out).expect("read clipboard");
     assert!(out.is_empty());
+
+    //Offsets are appended to existing output
+    html1.write_clipboard(&HTML).expect("write clipboard");
+    let mut bytes = b"prefix".to_vec();
+    let size = clipboard_win::raw::get_html(html1.code(), &mut bytes).expect("read html");
+    assert_eq!(size, HTML.len());
+    assert_eq!(bytes, b"prefix<tr>1</tr>");
+
+    //LF only header with broken offsets falls back to markers
+    const RAW: &str = "Version:0.9\nStartHTML:0000000000\nEndHTML:0000009999\nStartFragment:0000009000\nEndFragment:0000009999\n<html><body><!--StartFragment--><b>bold</b><!--EndFragment--></body></html>";
+    clipboard_win::raw::set(html1.code(), RAW.as_bytes()).expect("write raw html");
+    out.clear();
+    html1.read_clipboard(&mut out).expect("read clipboard");
+    assert_eq!(out, "<b>bold</b>");
+}
+
+fn should_read_decoded_rtf() {
+    //"Привет" in cp1251
+    const RTF: &[u8] = b"{\\rtf1\\ansi\\ansicpg1251 \xcf\xf0\xe8\xe2\xe5\xf2}\0";
+    const EXPECTED: &str = "{\\rtf1\\ansi\\ansicpg1251 Привет}";
+    let rtf = Rtf::new().expect("Create rtf");
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    rtf.write_clipboard(&RTF).expect("write clipboard");
+
+    let mut out = Vec::new();
+    rtf.read_clipboard(&mut out).expect("read clipboard");
+    assert_eq!(&out[..RTF.len()], RTF);
+
+    let mut out = String::new();
+    assert_eq!(rtf.read_decoded(&mut out).expect("read decoded"), EXPECTED.len());
+    assert_eq!(out, EXPECTED);
+
+    assert_eq!(Rtf::new().expect("Create rtf").code(), rtf.code());
+    let text: String = clipboard_win::get(rtf).expect("read string");
+    assert_eq!(text, EXPECTED);
+}
+
+fn should_work_with_office_shapes() {
+    const SHAPE: &[u8] = b"PK\x03\x04opaque shape";
+    let gvml = Office::gvml().expect("Create gvml");
+    assert_eq!(office::name_of(gvml.code()), Some(office::ART_GVML));
+    assert!(office::name_of(CF_TEXT).is_none());
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    gvml.write_clipboard(&SHAPE).expect("write clipboard");
+
+    assert!(is_format_avail(gvml.code()));
+    assert_eq!(office::enum_avail().collect::<Vec<_>>(), [(gvml.code(), office::ART_GVML)]);
+
+    let mut out = Vec::new();
+    assert_eq!(gvml.read_clipboard(&mut out).expect("read clipboard"), SHAPE.len());
+    assert_eq!(out, SHAPE);
 }
 
 macro_rules! run {
@@ -165,14 +1218,85 @@ macro_rules! run {
 fn clipboard_should_work() {
 
     run!(should_work_with_bitmap);
+    run!(should_work_with_top_down_bitmap);
     assert!(is_format_avail(CF_BITMAP));
     run!(should_work_with_string);
     assert!(is_format_avail(CF_UNICODETEXT));
     run!(should_set_file_list);
+    #[cfg(feature = "std")]
+    run!(should_set_file_list_path);
+    run!(should_get_file_list_from_dropfiles);
+    run!(should_clear);
+    #[cfg(feature = "std")]
+    run!(should_render_owner_display);
+    run!(should_set_many);
+    #[cfg(feature = "std")]
+    run!(should_render_delayed);
+    run!(should_work_with_ansi_text_format);
+    run!(should_enum_formats);
+    run!(should_set_get_dibv5);
+    run!(should_detect_change);
     assert!(is_format_avail(CF_HDROP));
     run!(should_work_with_wide_string);
+    run!(should_get_text_trimmed);
+    run!(should_read_dib_with_embedded_png);
     run!(should_work_with_bytes);
     run!(should_work_with_set_empty_string);
+    run!(should_set_text_and_bytes);
+    run!(should_snapshot_formats);
+    run!(should_work_with_wave);
+    run!(should_read_spreadsheet_text);
+    run!(should_get_text_best_effort);
+    run!(should_read_text_info);
+    run!(should_compute_content_hash);
+    run!(should_restore_snapshot);
+    run!(should_set_keep_formats);
+    run!(should_set_text_guarded);
+    run!(should_categorize_formats);
+    run!(should_work_with_url);
+    run!(should_export_and_import);
+    run!(should_pick_primary_format);
+    run!(should_reject_unpaired_surrogate);
+    run!(should_upgrade_text_to_unicode);
+    run!(should_hexdump_format);
+    run!(should_handle_empty_input);
+    run!(should_reject_empty_bitmap);
+    run!(should_put_and_take_clipboard_format);
+    run!(should_work_with_ansi_text);
+    run!(should_get_text_kind);
+    run!(should_set_text_crlf);
+    run!(should_read_image);
+    run!(should_work_with_gif);
+    run!(should_work_with_png);
+    #[cfg(feature = "monitor")]
+    run!(should_set_delayed_with_immediate);
+    #[cfg(feature = "std")]
+    run!(should_set_from_reader);
+    #[cfg(feature = "std")]
+    run!(should_set_file_and_url);
+    #[cfg(feature = "std")]
+    run!(should_wait_for_format);
+    run!(should_empty_open_clipboard);
+    run!(should_reject_nested_open);
+    run!(should_open_with_builder);
+    run!(should_open_with_timeout);
+    run!(should_work_within_open_clipboard);
     run!(should_set_owner);
     run!(should_set_get_html);
+    run!(should_work_with_office_shapes);
+    run!(should_read_decoded_rtf);
+}
+
+#[test]
+#[ignore]
+fn clipboard_should_work_with_huge_string() {
+    //Exceeds c_int, requiring conversion by chunks
+    let text = "メ".repeat(i32::MAX as usize / 3 + 16);
+
+    let _clip = Clipboard::new_attempts(10).expect("Open clipboard");
+    Unicode.write_clipboard(&text).expect("Write text");
+
+    let mut output = String::new();
+    assert_eq!(Unicode.read_clipboard(&mut output).expect("Read text"), text.len());
+    assert!(text == output);
 }